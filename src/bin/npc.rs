@@ -1,6 +1,23 @@
-use clap::Parser;
-use convertor::{IgnoreWordsConverter, PascalCaseReservedIdentifiersConverter};
+use std::{
+    io::BufRead,
+    path::{Path, PathBuf},
+};
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use convertor::{
+    IgnoreWordsConverter, InitialismsConverter, PascalCaseReservedIdentifiersConverter,
+};
+use corrector::{CharacterSet, InvalidCharacterCorrector};
+use dictionary::Dictionary;
+use error::NpcError;
+use language::Language;
 use npc::*;
+use pre_convertor::{
+    CollapseSeparatorsConverter, StripPrefixConverter, StripSuffixConverter,
+    TransliterateConverter, TrimConverter,
+};
+use rewrite::RewriteOptions;
+use word::{Acronym, Caseless, Digit, SplitOptions};
 
 fn main() {
     let cli = Cli::parse();
@@ -8,7 +25,10 @@ fn main() {
 }
 
 #[derive(Parser)]
+#[clap(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Cli {
+    #[clap(subcommand)]
+    command: Option<Command>,
     #[clap(short = 's', long = "snake")]
     snake: bool,
     #[clap(short = 'c', long = "camel")]
@@ -17,20 +37,209 @@ struct Cli {
     pascal: bool,
     #[clap(short = 'k', long = "chain")]
     chain: bool,
-    #[clap(short = 'o', long = "constant")]
+    #[clap(short = 'C', long = "constant")]
     constant: bool,
+    #[clap(long = "train")]
+    train: bool,
+    #[clap(long = "dot")]
+    dot: bool,
+    #[clap(long = "path")]
+    path: bool,
+    #[clap(long = "title")]
+    title: bool,
+    #[clap(long = "sentence")]
+    sentence_case: bool,
+    #[clap(long = "flat")]
+    flat: bool,
+    #[clap(long = "upper-flat")]
+    upper_flat: bool,
+    #[clap(long = "cobol")]
+    cobol: bool,
+    #[clap(long = "ada")]
+    ada: bool,
+    #[clap(long = "camel-snake")]
+    camel_snake: bool,
     #[clap(short = 'i', long = "ignores")]
     ignores: Vec<String>,
     #[clap(long = "consider-well-known")]
     consider_well_known: bool,
     #[clap(long = "consider-words")]
     consider_words: Vec<String>,
-    #[clap(help = "The sentence to convert")]
-    sentence: String,
+    #[clap(
+        long = "dictionary",
+        help = "Load reserved words from a newline list or TOML file, can be given several times"
+    )]
+    dictionaries: Vec<PathBuf>,
+    #[clap(
+        long = "dict",
+        value_delimiter = ',',
+        value_parser = parse_dictionary,
+        help = "Use bundled dictionaries of reserved words, like --dict cloud,db"
+    )]
+    dicts: Vec<Dictionary>,
+    #[clap(
+        long = "consider-initialisms",
+        help = "Render Go lint initialisms like ID, URL and HTTP in upper case for camel and pascal case"
+    )]
+    consider_initialisms: bool,
+    #[clap(long = "initialism-words")]
+    initialism_words: Vec<String>,
+    #[clap(
+        long = "split-acronyms",
+        help = "Split every upper case character of acronyms, HTTPServer to h_t_t_p_server"
+    )]
+    split_acronyms: bool,
+    #[clap(
+        long = "digits",
+        value_enum,
+        default_value = "attach",
+        help = "Where to split digits, attach: md5_hash, separate: md_5_hash, between-letters: ipv_4_address"
+    )]
+    digits: DigitPolicy,
+    #[clap(
+        long = "reject-caseless",
+        help = "Fail if the input has caseless characters like Japanese or Chinese"
+    )]
+    reject_caseless: bool,
+    #[clap(
+        long = "trim",
+        help = "Remove white spaces and separators at the head and the tail before the conversion"
+    )]
+    trim: bool,
+    #[clap(
+        long = "strip-prefix",
+        help = "Remove the prefix like m_ or I before the conversion, can be given several times"
+    )]
+    strip_prefixes: Vec<String>,
+    #[clap(
+        long = "strip-suffix",
+        help = "Remove the suffix like _t or Impl before the conversion, can be given several times"
+    )]
+    strip_suffixes: Vec<String>,
+    #[clap(
+        long = "collapse-separators",
+        help = "Replace a run of separators with one separator before the conversion"
+    )]
+    collapse_separators: bool,
+    #[clap(
+        long = "transliterate",
+        help = "Replace non-ASCII letters with ASCII ones before the conversion, café_crème to cafe_creme"
+    )]
+    transliterate: bool,
+    #[clap(
+        long = "romanize-kana",
+        help = "Romanize Japanese kana too, ユーザー to yuza, implies --transliterate"
+    )]
+    romanize_kana: bool,
+    #[clap(
+        long = "correct-invalid-characters",
+        help = "Replace characters which are invalid for identifiers like : or @ before the conversion"
+    )]
+    correct_invalid_characters: bool,
+    #[clap(
+        long = "character-set",
+        value_enum,
+        help = "Also replace characters out of the set, xid: Unicode identifier characters, ascii: ASCII alphanumerics and _, implies --correct-invalid-characters"
+    )]
+    character_set: Option<CharacterSetPolicy>,
+    #[clap(
+        long = "language",
+        value_parser = parse_language,
+        help = "Escape the result if it is a keyword of the language, like r#type for rust"
+    )]
+    language: Option<Language>,
+    #[clap(
+        short = 'f',
+        long = "file",
+        help = "Convert every identifier in the file, string literals and comments are kept. The language is inferred from the extension unless --language is given",
+        conflicts_with = "sentence"
+    )]
+    file: Option<PathBuf>,
+    #[clap(
+        long = "include-strings",
+        help = "Convert identifiers in string literals of the file too",
+        requires = "file"
+    )]
+    include_strings: bool,
+    #[clap(
+        long = "include-comments",
+        help = "Convert identifiers in comments of the file too",
+        requires = "file"
+    )]
+    include_comments: bool,
+    #[clap(
+        short = 'o',
+        long = "output",
+        help = "Write the converted file to the path instead of stdout",
+        requires = "file"
+    )]
+    output: Option<PathBuf>,
+    #[clap(help = "The sentence to convert", required_unless_present = "file")]
+    sentence: Option<String>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum DigitPolicy {
+    Attach,
+    Separate,
+    BetweenLetters,
+}
+
+impl From<DigitPolicy> for Digit {
+    fn from(policy: DigitPolicy) -> Self {
+        match policy {
+            DigitPolicy::Attach => Digit::Attach,
+            DigitPolicy::Separate => Digit::Separate,
+            DigitPolicy::BetweenLetters => Digit::BetweenLetters,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum CharacterSetPolicy {
+    Xid,
+    Ascii,
+}
+
+impl From<CharacterSetPolicy> for CharacterSet {
+    fn from(policy: CharacterSetPolicy) -> Self {
+        match policy {
+            CharacterSetPolicy::Xid => CharacterSet::Xid,
+            CharacterSetPolicy::Ascii => CharacterSet::Ascii,
+        }
+    }
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print the detected naming principals of each identifier
+    Detect(DetectArgs),
+}
+
+#[derive(Args)]
+struct DetectArgs {
+    #[clap(long = "json", help = "Print the result as JSON")]
+    json: bool,
+    #[clap(help = "Identifiers to detect, read from stdin line by line if not given")]
+    identifiers: Vec<String>,
 }
 
 impl Cli {
     fn run(&self) {
+        if let Some(Command::Detect(args)) = &self.command {
+            args.run();
+            return;
+        }
+        let param = self.parameter();
+        match &self.file {
+            Some(file) => self.run_file(file, param),
+            None => match try_convert(&param) {
+                Ok(result) => println!("{}", self.escape(result)),
+                Err(e) => exit_with(&e),
+            },
+        }
+    }
+    fn parameter(&self) -> Parameter {
         let principal = if self.snake {
             Principal::Snake
         } else if self.camel {
@@ -41,18 +250,48 @@ impl Cli {
             Principal::Chain
         } else if self.constant {
             Principal::Constant
+        } else if self.train {
+            Principal::Train
+        } else if self.dot {
+            Principal::Dot
+        } else if self.path {
+            Principal::Path
+        } else if self.title {
+            Principal::Title
+        } else if self.sentence_case {
+            Principal::Sentence
+        } else if self.flat {
+            Principal::Flat
+        } else if self.upper_flat {
+            Principal::UpperFlat
+        } else if self.cobol {
+            Principal::Cobol
+        } else if self.ada {
+            Principal::Ada
+        } else if self.camel_snake {
+            Principal::CamelSnake
         } else {
             Principal::Snake
         };
-        let param = Parameter::new(&self.sentence, principal);
-        let mut convertors: Vec<Box<dyn PostConvert>> = Vec::new();
-        if self.consider_well_known {
-            let mut well_known = PascalCaseReservedIdentifiersConverter::wellknown();
-            for word in &self.consider_words {
-                well_known = well_known.add(word);
-            }
-            convertors.push(well_known.to_convertor());
-        }
+        let acronym = if self.split_acronyms {
+            Acronym::Split
+        } else {
+            Acronym::Keep
+        };
+        let caseless = if self.reject_caseless {
+            Caseless::Reject
+        } else {
+            Caseless::Keep
+        };
+        let param = Parameter::new(self.sentence.as_deref().unwrap_or_default(), principal)
+            .change_split_options(
+                SplitOptions::new()
+                    .acronym(acronym)
+                    .digit(self.digits.into())
+                    .caseless(caseless),
+            );
+        let mut pre_convertors: Vec<Box<dyn PreConvert>> = Vec::new();
+        // ignored words are masked first, so the following pre-converters keep them
         if !self.ignores.is_empty() {
             let args = self
                 .ignores
@@ -60,11 +299,352 @@ impl Cli {
                 .map(|s| s.as_str())
                 .collect::<Vec<&str>>();
             let ignores = IgnoreWordsConverter::new(&args);
-            convertors.push(ignores.to_convertor());
+            pre_convertors.push(ignores.to_convertor());
+        }
+        if self.trim {
+            pre_convertors.push(TrimConverter::new().to_convertor());
+        }
+        if self.romanize_kana {
+            pre_convertors.push(TransliterateConverter::new().romanize_kana().to_convertor());
+        } else if self.transliterate {
+            pre_convertors.push(TransliterateConverter::new().to_convertor());
+        }
+        if self.correct_invalid_characters || self.character_set.is_some() {
+            let mut corrector = InvalidCharacterCorrector::default();
+            if let Some(character_set) = self.character_set {
+                corrector.change_character_set(character_set.into());
+            }
+            pre_convertors.push(corrector.to_convertor());
+        }
+        if !self.strip_prefixes.is_empty() {
+            let args = self
+                .strip_prefixes
+                .iter()
+                .map(|s| s.as_str())
+                .collect::<Vec<&str>>();
+            pre_convertors.push(StripPrefixConverter::new(&args).to_convertor());
+        }
+        if !self.strip_suffixes.is_empty() {
+            let args = self
+                .strip_suffixes
+                .iter()
+                .map(|s| s.as_str())
+                .collect::<Vec<&str>>();
+            pre_convertors.push(StripSuffixConverter::new(&args).to_convertor());
+        }
+        if self.collapse_separators {
+            pre_convertors.push(CollapseSeparatorsConverter::new().to_convertor());
+        }
+        let param = pre_convertors
+            .into_iter()
+            .fold(param, |acc, c| acc.add_pre_convert(c));
+        let mut convertors: Vec<Box<dyn PostConvert>> = Vec::new();
+        if self.consider_well_known || !self.dictionaries.is_empty() || !self.dicts.is_empty() {
+            let mut reserved = if self.consider_well_known {
+                PascalCaseReservedIdentifiersConverter::wellknown()
+            } else {
+                PascalCaseReservedIdentifiersConverter::default()
+            };
+            for dict in &self.dicts {
+                reserved = reserved.add_dictionary(*dict);
+            }
+            for dictionary in &self.dictionaries {
+                let loaded = PascalCaseReservedIdentifiersConverter::from_file(dictionary)
+                    .unwrap_or_else(|e| {
+                        eprintln!("failed to load {}: {}", dictionary.display(), e);
+                        std::process::exit(1);
+                    });
+                reserved = reserved.merge(loaded);
+            }
+            for word in &self.consider_words {
+                reserved = reserved.add(word);
+            }
+            convertors.push(reserved.to_convertor());
         }
-        let param = convertors
+        if self.consider_initialisms {
+            let mut initialisms = InitialismsConverter::golint();
+            for word in &self.initialism_words {
+                initialisms = initialisms.add(word);
+            }
+            convertors.push(initialisms.to_convertor());
+        }
+        convertors
             .into_iter()
-            .fold(param, |acc, c| acc.add_post_convert(c));
-        println!("{}", convert(&param));
+            .fold(param, |acc, c| acc.add_post_convert(c))
+    }
+    fn escape(&self, identifier: String) -> String {
+        match self.language {
+            Some(language) => language.escape(&identifier),
+            None => identifier,
+        }
+    }
+    // keywords and strings can not be told apart without the language
+    fn file_language(&self, file: &Path) -> Option<Language> {
+        self.language.or_else(|| {
+            file.extension()
+                .and_then(|e| e.to_str())
+                .and_then(Language::from_extension)
+        })
+    }
+    fn run_file(&self, file: &PathBuf, param: Parameter) {
+        let text = std::fs::read_to_string(file).unwrap_or_else(|e| {
+            eprintln!("failed to read {}: {}", file.display(), e);
+            std::process::exit(1);
+        });
+        let language = self.file_language(file).unwrap_or_else(|| {
+            eprintln!(
+                "unknown language of {}, give it with --language",
+                file.display()
+            );
+            std::process::exit(1);
+        });
+        let options = RewriteOptions::new()
+            .include_strings(self.include_strings)
+            .include_comments(self.include_comments)
+            .language(language);
+        let result = try_rewrite_text(&text, &param, options).unwrap_or_else(|e| exit_with(&e));
+        match &self.output {
+            Some(output) => std::fs::write(output, result).unwrap_or_else(|e| {
+                eprintln!("failed to write {}: {}", output.display(), e);
+                std::process::exit(1);
+            }),
+            None => print!("{}", result),
+        }
+    }
+}
+
+fn parse_language(name: &str) -> Result<Language, String> {
+    Language::from_name(name).ok_or_else(|| {
+        let names = Language::all()
+            .iter()
+            .map(|l| l.name())
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("unknown language {}, available: {}", name, names)
+    })
+}
+
+fn parse_dictionary(name: &str) -> Result<Dictionary, String> {
+    Dictionary::from_name(name).ok_or_else(|| {
+        let names = Dictionary::all()
+            .iter()
+            .map(|d| d.name())
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("unknown dictionary {}, available: {}", name, names)
+    })
+}
+
+fn exit_with(e: &NpcError) -> ! {
+    eprintln!("error: {}", e);
+    let code = match e {
+        NpcError::EmptyInput => 3,
+        NpcError::UnsupportedInput(_) => 4,
+        NpcError::InvalidIdentifier { .. } => 5,
+        NpcError::CaselessInput(_) => 6,
+    };
+    std::process::exit(code)
+}
+
+impl DetectArgs {
+    fn run(&self) {
+        let identifiers = if self.identifiers.is_empty() {
+            std::io::stdin()
+                .lock()
+                .lines()
+                .map(|line| {
+                    line.unwrap_or_else(|e| {
+                        eprintln!("failed to read stdin: {}", e);
+                        std::process::exit(1);
+                    })
+                })
+                .collect()
+        } else {
+            self.identifiers.clone()
+        };
+        let detections = identifiers
+            .iter()
+            .map(|identifier| (identifier.as_str(), detect(identifier)))
+            .collect::<Vec<_>>();
+        if self.json {
+            println!("{}", detections_to_json(&detections));
+        } else {
+            print!("{}", detections_to_plain(&detections));
+        }
+    }
+}
+
+// one line for each identifier, the identifier, the best principal and the candidates separated by tabs
+fn detections_to_plain(detections: &[(&str, Detection)]) -> String {
+    detections
+        .iter()
+        .map(|(identifier, detection)| {
+            format!(
+                "{}\t{}\t{}\n",
+                identifier,
+                detection.best().map(principal_name).unwrap_or("unknown"),
+                detection
+                    .candidates()
+                    .iter()
+                    .map(|p| principal_name(*p))
+                    .collect::<Vec<_>>()
+                    .join(",")
+            )
+        })
+        .collect()
+}
+
+fn detections_to_json(detections: &[(&str, Detection)]) -> String {
+    let objects = detections
+        .iter()
+        .map(|(identifier, detection)| {
+            let best = detection
+                .best()
+                .map(|p| format!("\"{}\"", principal_name(p)))
+                .unwrap_or_else(|| "null".to_string());
+            let candidates = detection
+                .candidates()
+                .iter()
+                .map(|p| format!("\"{}\"", principal_name(*p)))
+                .collect::<Vec<_>>()
+                .join(",");
+            format!(
+                "{{\"input\":{},\"best\":{},\"candidates\":[{}],\"ambiguous\":{}}}",
+                json_string(identifier),
+                best,
+                candidates,
+                detection.is_ambiguous()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    format!("[{}]", objects)
+}
+
+fn json_string(source: &str) -> String {
+    let mut result = String::from("\"");
+    for c in source.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if c.is_control() => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+fn principal_name(principal: Principal) -> &'static str {
+    match principal {
+        Principal::Camel => "camel",
+        Principal::Pascal => "pascal",
+        Principal::Snake => "snake",
+        Principal::Constant => "constant",
+        Principal::Chain => "chain",
+        Principal::Train => "train",
+        Principal::Dot => "dot",
+        Principal::Path => "path",
+        Principal::Title => "title",
+        Principal::Sentence => "sentence",
+        Principal::Flat => "flat",
+        Principal::UpperFlat => "upper_flat",
+        Principal::Cobol => "cobol",
+        Principal::Ada => "ada",
+        Principal::CamelSnake => "camel_snake",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detections<'a>(identifiers: &[&'a str]) -> Vec<(&'a str, Detection)> {
+        identifiers.iter().map(|i| (*i, detect(i))).collect()
+    }
+    fn convert_args(args: &[&str]) -> Result<String, NpcError> {
+        let cli = Cli::parse_from(std::iter::once("npc").chain(args.iter().copied()));
+        try_convert(&cli.parameter())
+    }
+    #[test]
+    fn ignored_words_are_kept_by_the_other_pre_converters() {
+        let args = [
+            "--pascal",
+            "--correct-invalid-characters",
+            "--ignores",
+            "my-App",
+            "use_my-App_config",
+        ];
+        assert_eq!(convert_args(&args), Ok("Usemy-AppConfig".to_string()));
+        let args = [
+            "--snake",
+            "--transliterate",
+            "--ignores",
+            "café",
+            "café_crèmeLatte",
+        ];
+        assert_eq!(convert_args(&args), Ok("café_creme_latte".to_string()));
+        let args = [
+            "--snake",
+            "--character-set",
+            "ascii",
+            "--trim",
+            "--ignores",
+            "Größe",
+            " Größe_Straße ",
+        ];
+        assert_eq!(convert_args(&args), Ok("Größe_strasse".to_string()));
+    }
+    #[test]
+    fn infer_language_of_file_from_extension() {
+        let language = |args: &[&str], file: &str| {
+            Cli::parse_from(std::iter::once("npc").chain(args.iter().copied()))
+                .file_language(Path::new(file))
+        };
+        assert_eq!(
+            language(&["--snake", "-f", "hello.py"], "hello.py"),
+            Some(Language::Python)
+        );
+        assert_eq!(
+            language(&["--snake", "-f", "x.rs"], "x.rs"),
+            Some(Language::Rust)
+        );
+        assert_eq!(
+            language(&["--snake", "--language", "go", "-f", "x.rs"], "x.rs"),
+            Some(Language::Go)
+        );
+        assert_eq!(language(&["--snake", "-f", "notes"], "notes"), None);
+    }
+    #[test]
+    fn print_detections_separated_by_tabs() {
+        assert_eq!(
+            detections_to_plain(&detections(&["userName", "user", "!!"])),
+            "userName\tcamel\tcamel\n\
+             user\tflat\tcamel,snake,chain,dot,path,flat,camel_snake\n\
+             !!\tunknown\t\n"
+        );
+        assert_eq!(detections_to_plain(&[]), "");
+    }
+    #[test]
+    fn print_detections_as_json() {
+        assert_eq!(
+            detections_to_json(&detections(&["userName", "!!"])),
+            "[{\"input\":\"userName\",\"best\":\"camel\",\"candidates\":[\"camel\"],\"ambiguous\":false},\
+             {\"input\":\"!!\",\"best\":null,\"candidates\":[],\"ambiguous\":false}]"
+        );
+        assert_eq!(detections_to_json(&[]), "[]");
+    }
+    #[test]
+    fn escape_json_strings() {
+        assert_eq!(json_string("user\"name\""), "\"user\\\"name\\\"\"");
+        assert_eq!(json_string("user\\name"), "\"user\\\\name\"");
+        assert_eq!(
+            json_string("a\nb\rc\td\u{1}e\u{7f}"),
+            "\"a\\nb\\rc\\td\\u0001e\\u007f\""
+        );
+        assert_eq!(json_string("ユーザー"), "\"ユーザー\"");
     }
 }
@@ -1,36 +1,203 @@
-use crate::{to_camel, to_chain, to_constant, to_pascal, to_snake, PostConvert, Principal};
+use std::{
+    io::{self, Read},
+    path::Path,
+};
+
+use crate::{
+    dictionary::Dictionary,
+    trie::Trie,
+    word::{map_unmasked, mask, split_words, Word, WordKind},
+    PostConvert, PreConvert, Principal,
+};
 
 #[derive(Debug, Clone)]
 struct PascalCaseReservedIdentifiers {
     inner: Vec<String>,
+    // lower case identifiers to the index of inner
+    trie: Trie,
 }
 
 impl PascalCaseReservedIdentifiers {
     fn new() -> Self {
-        Self { inner: Vec::new() }
+        Self {
+            inner: Vec::new(),
+            trie: Trie::new(),
+        }
     }
     fn wellknown() -> Self {
         let mut this = Self::new();
-        vec!["GitHub", "TypeScript", "JavaScript", "DeepL", "ChatGPT"]
+        ["GitHub", "TypeScript", "JavaScript", "DeepL", "ChatGPT"]
             .iter()
             .for_each(|s| this.add(*s));
         this
     }
+    // the first one is kept if identifiers differ only in case, like `GitHub` and `Github`
     fn add(&mut self, target: impl Into<String>) {
         let target = target.into();
-        self.inner.push(target);
+        if self.trie.insert(&target.to_lowercase(), self.inner.len()) {
+            self.inner.push(target);
+        }
+    }
+    // replace each run of words which spells an identifier with the identifier
+    fn replace_runs(&self, words: Vec<Word>) -> Vec<Word> {
+        let mut result = Vec::with_capacity(words.len());
+        let mut i = 0;
+        while i < words.len() {
+            match self.find_run(&words, i) {
+                Some((index, end, trailing)) => {
+                    let value = format!("{}{}", self.inner[index], trailing);
+                    let word = Word::new(value, words[i].start(), words[end - 1].end());
+                    result.push(word.with_kind(WordKind::Reserved));
+                    i = end;
+                }
+                None => {
+                    result.push(words[i].clone());
+                    i += 1;
+                }
+            }
+        }
+        result
+    }
+    // find the longest run of words from start which spells an identifier ignoring case.
+    // words of the run must be next to each other in the source, so `i_am` is not `IAM`,
+    // and an identifier without lower case is only one word, so `iAm` is not `IAM` but `iam` is.
+    // characters which are not alphanumeric are allowed at the tail of the last word, like `!` of `GitHub!`.
+    // returns the index of the identifier, the end of the run and those trailing characters
+    fn find_run<'a>(&self, words: &'a [Word], start: usize) -> Option<(usize, usize, &'a str)> {
+        let mut node = Trie::ROOT;
+        let mut found = None;
+        for (j, word) in words.iter().enumerate().skip(start) {
+            if word.kind() != WordKind::Normal || j > start && word.start() != words[j - 1].end() {
+                break;
+            }
+            let lower = word.as_str().to_lowercase();
+            for (k, c) in lower.char_indices() {
+                node = match self.trie.step(node, c) {
+                    Some(next) => next,
+                    None => return found,
+                };
+                let rest = &lower[k + c.len_utf8()..];
+                if let Some(index) = self.trie.pattern(node) {
+                    let joinable = j == start || self.inner[index].chars().any(char::is_lowercase);
+                    if joinable && !rest.chars().any(|c| c.is_alphanumeric()) {
+                        let trailing = &word.as_str()[word.as_str().len() - rest.len()..];
+                        found = Some((index, j + 1, trailing));
+                    }
+                }
+            }
+        }
+        found
+    }
+    // newline list like below, or TOML with `words = ["PostgreSQL", "OAuth"]`
+    // ```text
+    // # comment
+    // PostgreSQL
+    // OAuth
+    // ```
+    fn parse(text: &str) -> io::Result<Self> {
+        let is_toml = text
+            .lines()
+            .map(str::trim)
+            .any(|line| !line.starts_with('#') && (line.starts_with('[') || line.contains('=')));
+        let mut this = Self::new();
+        if !is_toml {
+            text.lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .for_each(|line| this.add(line));
+            return Ok(this);
+        }
+        let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+        let table = text
+            .parse::<toml::Table>()
+            .map_err(|e| invalid(e.to_string()))?;
+        let words = match table.get("words") {
+            Some(toml::Value::Array(words)) => words,
+            _ => return Err(invalid("dictionary needs `words` array".to_string())),
+        };
+        for word in words {
+            match word.as_str() {
+                Some(word) => this.add(word),
+                None => return Err(invalid(format!("{:?} is not a string", word))),
+            }
+        }
+        Ok(this)
     }
 }
 
+/// PascalCaseReservedIdentifiersConverter keeps the casing of reserved identifiers like `GitHub`.
+///
+/// A run of words spelling a reserved identifier in any case, like `git`, `hub` or `github`,
+/// is replaced with the identifier, so it is rendered `GitHub` in PascalCase and `github` in snake_case.
 pub struct PascalCaseReservedIdentifiersConverter {
     identifiers: PascalCaseReservedIdentifiers,
-    invalids_pascal: Vec<String>,
-    invalids_camel: Vec<String>,
+    head: ReservedCase,
+    middle: ReservedCase,
+}
+
+/// ReservedCase decides how a reserved identifier starting with lower case, like `iOS`,
+/// is rendered in camelCase and PascalCase names, where its canonical casing would hide the word boundary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReservedCase {
+    /// `iOS`, `macOS`
+    Canonical,
+    /// the first character in upper case, `IOS`, `MacOS`
+    Capitalized,
+    /// `IOS`, `MACOS`
+    Upper,
+    /// `ios`, `macos`
+    Lower,
+}
+
+impl ReservedCase {
+    fn apply(&self, canonical: &str) -> String {
+        match self {
+            Self::Canonical => canonical.to_string(),
+            Self::Capitalized => {
+                let mut chars = canonical.chars();
+                chars.next().map_or(String::new(), |first| {
+                    first.to_uppercase().chain(chars).collect()
+                })
+            }
+            Self::Upper => canonical.to_uppercase(),
+            Self::Lower => canonical.to_lowercase(),
+        }
+    }
 }
 
 impl PostConvert for PascalCaseReservedIdentifiersConverter {
-    fn convert(&self, source: &str, principal: Principal) -> String {
-        self.to(source, principal)
+    fn convert(&self, words: Vec<Word>, principal: Principal) -> Vec<Word> {
+        let words = self.identifiers.replace_runs(words);
+        if !matches!(principal, Principal::Camel | Principal::Pascal) {
+            return words;
+        }
+        words
+            .into_iter()
+            .enumerate()
+            .map(|(i, word)| {
+                let starts_lower = word.as_str().starts_with(char::is_lowercase);
+                if word.kind() != WordKind::Reserved || !starts_lower {
+                    return word;
+                }
+                let (case, kind) = if i == 0 && principal == Principal::Camel {
+                    // the head of camelCase is lower case unless it is told otherwise
+                    let kind = match self.head {
+                        ReservedCase::Lower => WordKind::Reserved,
+                        _ => WordKind::Verbatim,
+                    };
+                    (self.head, kind)
+                } else {
+                    (self.middle, WordKind::Reserved)
+                };
+                Word::new(case.apply(word.as_str()), word.start(), word.end()).with_kind(kind)
+            })
+            .collect()
+    }
+}
+
+impl Default for PascalCaseReservedIdentifiersConverter {
+    fn default() -> Self {
+        Self::new(PascalCaseReservedIdentifiers::new())
     }
 }
 
@@ -41,132 +208,222 @@ impl PascalCaseReservedIdentifiersConverter {
     pub fn wellknown() -> Self {
         Self::new(PascalCaseReservedIdentifiers::wellknown())
     }
-    fn new(identifiers: PascalCaseReservedIdentifiers) -> Self {
-        let invalids_pascal = identifiers
-            .inner
+    /// from_file loads reserved identifiers from a dictionary file.
+    ///
+    /// The dictionary is a list of identifiers, one per line with `#` comments,
+    /// or TOML which has `words = ["PostgreSQL", "OAuth"]`.
+    pub fn from_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::from_reader(std::fs::File::open(path)?)
+    }
+    /// from_reader is the same as `from_file` but reads the dictionary from reader
+    pub fn from_reader(mut reader: impl Read) -> io::Result<Self> {
+        let mut text = String::new();
+        reader.read_to_string(&mut text)?;
+        Ok(Self::new(PascalCaseReservedIdentifiers::parse(&text)?))
+    }
+    /// add_dictionary adds the identifiers of the bundled dictionary
+    pub fn add_dictionary(mut self, dictionary: Dictionary) -> Self {
+        dictionary
+            .words()
             .iter()
-            .map(|s| Self::reserved_to_invalid_pascal(s))
-            .collect();
-        let invalids_camel = identifiers
+            .for_each(|word| self.identifiers.add(*word));
+        self
+    }
+    /// merge adds the identifiers of other which are not in self
+    pub fn merge(mut self, other: Self) -> Self {
+        other
+            .identifiers
             .inner
-            .iter()
-            .map(|s| Self::reserved_to_invalid_camel(s))
-            .collect();
+            .into_iter()
+            .for_each(|target| self.identifiers.add(target));
+        self
+    }
+    fn new(identifiers: PascalCaseReservedIdentifiers) -> Self {
         Self {
             identifiers,
-            invalids_pascal,
-            invalids_camel,
+            head: ReservedCase::Lower,
+            middle: ReservedCase::Capitalized,
         }
     }
-    pub fn add(mut self, target: impl Into<String>) -> Self {
-        let target = target.into();
-        self.identifiers.add(target.clone());
-        self.invalids_pascal
-            .push(Self::reserved_to_invalid_pascal(&target));
-        self.invalids_camel
-            .push(Self::reserved_to_invalid_camel(&target));
-
+    /// head decides the case of reserved identifiers starting with lower case at the head of camelCase names,
+    /// `ReservedCase::Lower` by default, like `iosVersion`
+    pub fn head(mut self, case: ReservedCase) -> Self {
+        self.head = case;
         self
     }
-    fn reserved_to_invalid_pascal(source: &str) -> String {
-        to_pascal(&source.to_lowercase())
+    /// middle decides the case of reserved identifiers starting with lower case at the other positions
+    /// of camelCase and PascalCase names, `ReservedCase::Capitalized` by default, like `userIOSVersion`
+    pub fn middle(mut self, case: ReservedCase) -> Self {
+        self.middle = case;
+        self
     }
-    fn reserved_to_invalid_camel(source: &str) -> String {
-        to_camel(&source)
+    #[allow(clippy::should_implement_trait)]
+    pub fn add(mut self, target: impl Into<String>) -> Self {
+        self.identifiers.add(target);
+        self
     }
-    pub fn to(&self, source: &str, principal: Principal) -> String {
-        match principal {
-            Principal::Snake => self.fix_for_snake_case(source),
-            Principal::Chain => self.fix_for_chain_case(source),
-            Principal::Constant => self.fix_for_constant_case(source),
-            Principal::Pascal => self.fix_for_pascal_case(source),
-            Principal::Camel => self.fix_for_camel_case(source),
-        }
-    }
-    fn fix_for_snake_case(&self, sentence: impl Into<String>) -> String {
-        let mut result = sentence.into();
-        for target in self.identifiers.inner.iter() {
-            let chain_case = to_snake(target);
-            if result.contains(&chain_case) {
-                result = result.replace(&chain_case, target.to_lowercase().as_str());
+}
+
+/// IgnoreWordsConverter keeps the listed words as they are for every principal.
+///
+/// It is a `PreConvert` which masks the ignored words in the source,
+/// so each of them is split out as one verbatim word and the rest is converted as usual.
+/// ## Example
+/// ```rust
+/// use npc::{convert, convertor::IgnoreWordsConverter, Parameter, Principal};
+///
+/// let ignores = IgnoreWordsConverter::new(&["HelloWorld"]);
+/// let param = Parameter::new("HelloWorld! GoodBye", Principal::Snake)
+///     .add_pre_convert(ignores.to_convertor());
+/// assert_eq!(convert(&param), "HelloWorld!_good_bye");
+/// ```
+pub struct IgnoreWordsConverter {
+    inner: Vec<String>,
+    trie: Trie,
+}
+impl IgnoreWordsConverter {
+    pub fn new(inner: &[&str]) -> Self {
+        let mut this = Self {
+            inner: Vec::new(),
+            trie: Trie::new(),
+        };
+        for word in inner {
+            if this.trie.insert(word, this.inner.len()) {
+                this.inner.push(word.to_string());
             }
         }
-        result
+        this
     }
-    fn fix_for_chain_case(&self, sentence: impl Into<String>) -> String {
-        let mut result = sentence.into();
-        for target in self.identifiers.inner.iter() {
-            let chain_case = to_chain(target);
-            if result.contains(&chain_case) {
-                result = result.replace(&chain_case, target.to_lowercase().as_str());
-            }
-        }
-        result
+    pub fn to_convertor(self) -> Box<Self> {
+        Box::new(self)
     }
+}
 
-    fn fix_for_constant_case(&self, sentence: impl Into<String>) -> String {
-        let mut result = sentence.into();
-        for target in self.identifiers.inner.iter() {
-            let constant_case = to_constant(target);
-            if result.contains(&constant_case) {
-                result = result.replace(&constant_case, target.to_uppercase().as_str());
+impl IgnoreWordsConverter {
+    fn mask_words(&self, source: &str) -> String {
+        let word_starts = split_words(source)
+            .iter()
+            .map(|w| w.start())
+            .collect::<Vec<_>>();
+        // an ignored word must not be a part of a word, like `Hello` of `Helloworld`
+        let is_edge = |i: usize| {
+            let before = source[..i].chars().next_back();
+            let after = source[i..].chars().next();
+            match (before, after) {
+                (Some(b), Some(a)) => {
+                    !b.is_alphanumeric() || !a.is_alphanumeric() || word_starts.contains(&i)
+                }
+                _ => true,
             }
-        }
-        result
-    }
-    fn fix_for_camel_case(&self, sentence: impl Into<String>) -> String {
-        let mut result = to_camel(sentence.into().as_str());
-        for target in self.invalids_camel.iter() {
-            if result.contains(target) {
-                result = result.replace(target, target.to_lowercase().as_str());
+        };
+        let mut result = String::with_capacity(source.len());
+        let mut i = 0;
+        while let Some(c) = source[i..].chars().next() {
+            let found = if is_edge(i) {
+                self.trie
+                    .longest_prefix(&source[i..], |end| is_edge(i + end))
+            } else {
+                None
+            };
+            match found {
+                Some((index, len)) => {
+                    result.push_str(&mask(&self.inner[index]));
+                    i += len;
+                }
+                None => {
+                    result.push(c);
+                    i += c.len_utf8();
+                }
             }
         }
         result
     }
+}
 
-    fn fix_for_pascal_case(&self, sentence: impl Into<String>) -> String {
-        let mut result = to_pascal(sentence.into().as_str());
-        for (i, target) in self.invalids_pascal.iter().enumerate() {
-            if result.contains(target) {
-                result = result.replace(target, self.identifiers.inner[i].as_str());
-            }
-        }
-        result
+// the words masked by another pre-converter are not masked again
+impl PreConvert for IgnoreWordsConverter {
+    fn convert(&self, source: &str, _: Principal) -> String {
+        map_unmasked(source, |part| self.mask_words(part))
     }
 }
 
-pub struct IgnoreWordsConverter {
+/// InitialismStyle decides how a known initialism is rendered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InitialismStyle {
+    /// `HTTPServerURL`
+    Upper,
+    /// `HttpServerUrl`
+    Capitalized,
+}
+
+/// InitialismsConverter renders known initialisms in camel and pascal case names.
+///
+/// The first word of a camel case name is always lower case, like `httpServerURL`.
+pub struct InitialismsConverter {
     inner: Vec<String>,
+    camel: InitialismStyle,
+    pascal: InitialismStyle,
 }
-impl IgnoreWordsConverter {
+
+impl InitialismsConverter {
     pub fn new(inner: &[&str]) -> Self {
         Self {
-            inner: inner.iter().map(|s| s.to_string()).collect(),
+            inner: inner.iter().map(|s| s.to_uppercase()).collect(),
+            camel: InitialismStyle::Upper,
+            pascal: InitialismStyle::Upper,
         }
     }
+    /// initialisms listed by Go lint
+    pub fn golint() -> Self {
+        Self::new(&[
+            "ACL", "API", "ASCII", "CPU", "CSS", "DNS", "EOF", "GUID", "HTML", "HTTP", "HTTPS",
+            "ID", "IP", "JSON", "LHS", "QPS", "RAM", "RHS", "RPC", "SLA", "SMTP", "SQL", "SSH",
+            "TCP", "TLS", "TTL", "UDP", "UI", "UID", "UUID", "URI", "URL", "UTF8", "VM", "XML",
+            "XMPP", "XSRF", "XSS",
+        ])
+    }
     pub fn to_convertor(self) -> Box<Self> {
         Box::new(self)
     }
-    fn fix_for<F: Fn(&str) -> String>(&self, converted: &str, convert_fn: F) -> String {
-        let mut result = converted.to_string();
-        for ignore_word in self.inner.iter() {
-            let converted_ignore_word = convert_fn(ignore_word);
-            if result.contains(&converted_ignore_word) {
-                result = result.replace(&converted_ignore_word, ignore_word);
-            }
+    #[allow(clippy::should_implement_trait)]
+    pub fn add(mut self, target: impl Into<String>) -> Self {
+        self.inner.push(target.into().to_uppercase());
+        self
+    }
+    pub fn style(mut self, principal: Principal, style: InitialismStyle) -> Self {
+        match principal {
+            Principal::Camel => self.camel = style,
+            Principal::Pascal => self.pascal = style,
+            _ => {}
         }
-        result
+        self
+    }
+    fn fix_for(&self, words: Vec<Word>, style: InitialismStyle) -> Vec<Word> {
+        words
+            .into_iter()
+            .map(|word| {
+                let upper = word.as_str().to_uppercase();
+                if word.kind() != WordKind::Normal || !self.inner.contains(&upper) {
+                    return word;
+                }
+                match style {
+                    InitialismStyle::Upper => {
+                        Word::new(upper, word.start(), word.end()).with_kind(WordKind::Reserved)
+                    }
+                    // normal words are capitalized by the principal
+                    InitialismStyle::Capitalized => word,
+                }
+            })
+            .collect()
     }
 }
 
-impl PostConvert for IgnoreWordsConverter {
-    fn convert(&self, source: &str, principal: Principal) -> String {
+impl PostConvert for InitialismsConverter {
+    fn convert(&self, words: Vec<Word>, principal: Principal) -> Vec<Word> {
         match principal {
-            Principal::Snake => self.fix_for(source, to_snake),
-            Principal::Chain => self.fix_for(source, to_chain),
-            Principal::Constant => self.fix_for(source, to_constant),
-            Principal::Pascal => self.fix_for(source, to_pascal),
-            Principal::Camel => self.fix_for(source, to_camel),
+            Principal::Camel => self.fix_for(words, self.camel),
+            Principal::Pascal => self.fix_for(words, self.pascal),
+            _ => words,
         }
     }
 }
@@ -174,7 +431,12 @@ impl PostConvert for IgnoreWordsConverter {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{convert, Parameter, Principal};
+    use crate::{
+        convert,
+        corrector::{CharacterSet, InvalidCharacterCorrector},
+        pre_convertor::TransliterateConverter,
+        Parameter, Principal,
+    };
 
     #[test]
     fn consider_reserved_words_in_pascal_case_names() {
@@ -206,25 +468,229 @@ mod tests {
         assert_eq!(convert(&params), "UKaiUseGitHubEnterpriseGitHub");
     }
     #[test]
+    fn reserved_words_replace_only_whole_words() {
+        let params = Parameter::new("MyGitHubberUsesGitHub", Principal::Snake)
+            .add_post_convert(PascalCaseReservedIdentifiersConverter::wellknown().to_convertor());
+        assert_eq!(convert(&params), "my_git_hubber_uses_github");
+
+        let params = params.change_principal(Principal::Pascal);
+        assert_eq!(convert(&params), "MyGitHubberUsesGitHub");
+
+        let params = Parameter::new("gitHub_typescript", Principal::Title)
+            .add_post_convert(PascalCaseReservedIdentifiersConverter::wellknown().to_convertor());
+        assert_eq!(convert(&params), "GitHub TypeScript");
+
+        // words split by separators are not joined
+        let params = params.change_source("git_hub_typescript");
+        assert_eq!(convert(&params), "Git Hub TypeScript");
+    }
+    #[test]
+    fn post_convert_receives_words() {
+        let params = Parameter::new("user_id", Principal::Pascal).add_post_convert(Box::new(
+            |words: Vec<Word>, _: Principal| {
+                words
+                    .into_iter()
+                    .map(|w| match w.as_str() {
+                        "id" => w.with_kind(WordKind::Verbatim),
+                        _ => w,
+                    })
+                    .collect::<Vec<_>>()
+            },
+        ));
+        assert_eq!(convert(&params), "Userid");
+    }
+    #[test]
+    fn load_reserved_words_from_dictionary() {
+        let list = "# products\nPostgreSQL\n\n  OAuth  \nGitHub\n";
+        let toml = "words = [\"YouTube\", \"OAuth\"]\n";
+        let dictionary = PascalCaseReservedIdentifiersConverter::from_reader(list.as_bytes())
+            .unwrap()
+            .merge(PascalCaseReservedIdentifiersConverter::from_reader(toml.as_bytes()).unwrap());
+        assert_eq!(
+            dictionary.identifiers.inner,
+            vec!["PostgreSQL", "OAuth", "GitHub", "YouTube"]
+        );
+        let params = Parameter::new("postgresql_oauth_youtube", Principal::Pascal)
+            .add_post_convert(dictionary.to_convertor());
+        assert_eq!(convert(&params), "PostgreSQLOAuthYouTube");
+
+        let err = PascalCaseReservedIdentifiersConverter::from_reader("words = 1".as_bytes());
+        assert_eq!(err.err().unwrap().kind(), io::ErrorKind::InvalidData);
+        let err = PascalCaseReservedIdentifiersConverter::from_file("not/found.toml");
+        assert_eq!(err.err().unwrap().kind(), io::ErrorKind::NotFound);
+    }
+    #[test]
+    fn reserved_words_starting_with_lower_case() {
+        let reserved = || {
+            PascalCaseReservedIdentifiersConverter::default()
+                .add("iOS")
+                .add("macOS")
+                .add("eBay")
+                .add("iPhone")
+        };
+        let params = |source: &str, to: Principal| {
+            Parameter::new(source, to).add_post_convert(reserved().to_convertor())
+        };
+        assert_eq!(
+            convert(&params("ios_version", Principal::Camel)),
+            "iosVersion"
+        );
+        assert_eq!(
+            convert(&params("user_ios_version", Principal::Camel)),
+            "userIOSVersion"
+        );
+        assert_eq!(
+            convert(&params("macos_version", Principal::Pascal)),
+            "MacOSVersion"
+        );
+        assert_eq!(
+            convert(&params("iOSVersion", Principal::Snake)),
+            "ios_version"
+        );
+        assert_eq!(
+            convert(&params("userMacOSVersion", Principal::Snake)),
+            "user_macos_version"
+        );
+        assert_eq!(
+            convert(&params("ebay_iphone_app", Principal::Constant)),
+            "EBAY_IPHONE_APP"
+        );
+        assert_eq!(
+            convert(&params("ebay_iphone_app", Principal::Title)),
+            "eBay iPhone App"
+        );
+        assert_eq!(
+            convert(&params("ebay_iphone_app", Principal::Train)),
+            "eBay-iPhone-App"
+        );
+
+        let params = |source: &str, to: Principal| {
+            Parameter::new(source, to).add_post_convert(
+                reserved()
+                    .head(ReservedCase::Canonical)
+                    .middle(ReservedCase::Canonical)
+                    .to_convertor(),
+            )
+        };
+        assert_eq!(
+            convert(&params("iphone_on_ios", Principal::Camel)),
+            "iPhoneOniOS"
+        );
+        assert_eq!(
+            convert(&params("iphone_on_ios", Principal::Pascal)),
+            "iPhoneOniOS"
+        );
+    }
+    #[test]
+    fn longest_reserved_word_wins_whatever_the_order() {
+        let source = "java_and_javascript_on_github";
+        for reserved in [
+            PascalCaseReservedIdentifiersConverter::default()
+                .add("Java")
+                .add("JavaScript")
+                .add("Git")
+                .add("GitHub"),
+            PascalCaseReservedIdentifiersConverter::default()
+                .add("GitHub")
+                .add("Git")
+                .add("JavaScript")
+                .add("Java"),
+        ] {
+            let params =
+                Parameter::new(source, Principal::Title).add_post_convert(reserved.to_convertor());
+            assert_eq!(convert(&params), "Java And JavaScript On GitHub");
+        }
+        // the first one is kept for identifiers only different in case
+        let reserved = PascalCaseReservedIdentifiersConverter::default()
+            .add("GitHub")
+            .add("Github");
+        assert_eq!(reserved.identifiers.inner, vec!["GitHub"]);
+
+        for ignores in [["Hello", "HelloWorld"], ["HelloWorld", "Hello"]] {
+            let params = Parameter::new("HelloWorld_Hello", Principal::Snake)
+                .add_pre_convert(IgnoreWordsConverter::new(&ignores).to_convertor());
+            assert_eq!(convert(&params), "HelloWorld_Hello");
+        }
+    }
+    #[test]
     fn consider_ignore_words() {
         let source = "HelloWorld! GoodBye";
         let ignores = IgnoreWordsConverter::new(&["HelloWorld"]);
         let params =
-            Parameter::new(source, Principal::Snake).add_post_convert(ignores.to_convertor());
-        // TODO:Fix
-        assert_eq!(convert(&params), "HelloWorld! _good_bye");
+            Parameter::new(source, Principal::Snake).add_pre_convert(ignores.to_convertor());
+        assert_eq!(convert(&params), "HelloWorld!_good_bye");
 
         let params = params.change_principal(Principal::Constant);
-        // TODO:Fix
-        assert_eq!(convert(&params), "HelloWorld! _GOOD_BYE");
+        assert_eq!(convert(&params), "HelloWorld!_GOOD_BYE");
 
-        // TODO:Fix
         let params = params.change_principal(Principal::Chain);
-        assert_eq!(convert(&params), "HelloWorld! -good-bye");
+        assert_eq!(convert(&params), "HelloWorld!-good-bye");
+
+        let params = params.change_principal(Principal::Camel);
+        assert_eq!(convert(&params), "HelloWorld!GoodBye");
+
+        let params = params.change_principal(Principal::Pascal);
+        assert_eq!(convert(&params), "HelloWorld!GoodBye");
+
+        let params = params.change_principal(Principal::Title);
+        assert_eq!(convert(&params), "HelloWorld! Good Bye");
+
+        let params = params.change_principal(Principal::Cobol);
+        assert_eq!(convert(&params), "HelloWorld!-GOOD-BYE");
+
+        // ignored words may have separators and are not a part of another word
+        let ignores = IgnoreWordsConverter::new(&["my-App", "Hello"]);
+        let params = Parameter::new("use_my-App_config_Helloworld", Principal::Pascal)
+            .add_pre_convert(ignores.to_convertor());
+        assert_eq!(convert(&params), "Usemy-AppConfigHelloworld");
+
+        let params = params.change_principal(Principal::Snake);
+        assert_eq!(convert(&params), "use_my-App_config_helloworld");
+
+        let params = Parameter::new("sayHelloWorld", Principal::Constant)
+            .add_pre_convert(IgnoreWordsConverter::new(&["Hello"]).to_convertor());
+        assert_eq!(convert(&params), "SAY_Hello_WORLD");
+        // the following pre-converters keep ignored words
+        let mut corrector = InvalidCharacterCorrector::default();
+        corrector.change_character_set(CharacterSet::Xid);
+        let params = Parameter::new("use_my-App_café:config", Principal::Pascal)
+            .add_pre_convert(IgnoreWordsConverter::new(&["my-App", "café"]).to_convertor())
+            .add_pre_convert(IgnoreWordsConverter::new(&["App"]).to_convertor())
+            .add_pre_convert(corrector.to_convertor())
+            .add_pre_convert(TransliterateConverter::new().to_convertor());
+        assert_eq!(convert(&params), "Usemy-AppcaféConfig");
+
+        // the private use characters of masks in the source are removed
+        let params = Parameter::new("\u{E000}user_name\u{E001}", Principal::Pascal);
+        assert_eq!(convert(&params), "UserName");
+        let params = Parameter::new("user\u{E000}Name", Principal::Snake)
+            .add_pre_convert(IgnoreWordsConverter::new(&["Name"]).to_convertor());
+        assert_eq!(convert(&params), "user_Name");
+    }
+    #[test]
+    fn consider_initialisms() {
+        let source = "http_server_url";
+        let params = Parameter::new(source, Principal::Pascal)
+            .add_post_convert(InitialismsConverter::golint().to_convertor());
+        assert_eq!(convert(&params), "HTTPServerURL");
 
-        //  TODO:Fix
         let params = params.change_principal(Principal::Camel);
-        assert_eq!(convert(&params), "HelloWorld! GoodBye");
+        assert_eq!(convert(&params), "httpServerURL");
+
+        let params = params.change_principal(Principal::Snake);
+        assert_eq!(convert(&params), "http_server_url");
+
+        let source = "user_id_json";
+        let params = Parameter::new(source, Principal::Pascal).add_post_convert(
+            InitialismsConverter::new(&["ID"])
+                .add("json")
+                .style(Principal::Pascal, InitialismStyle::Capitalized)
+                .to_convertor(),
+        );
+        assert_eq!(convert(&params), "UserIdJson");
+
+        let params = params.change_principal(Principal::Camel);
+        assert_eq!(convert(&params), "userIDJSON");
     }
     #[test]
     fn consider_pascal_case_names_with_wellknown_words() {
@@ -238,5 +704,26 @@ mod tests {
 
         let params = params.change_principal(Principal::Chain);
         assert_eq!(convert(&params), "use-github-enterprise-github");
+
+        let params = params.change_principal(Principal::Dot);
+        assert_eq!(convert(&params), "use.github.enterprise.github");
+
+        let params = params.change_principal(Principal::Train);
+        assert_eq!(convert(&params), "Use-GitHub-Enterprise-GitHub");
+
+        let params = params.change_principal(Principal::Title);
+        assert_eq!(convert(&params), "Use GitHub Enterprise GitHub");
+
+        let params = params.change_principal(Principal::Sentence);
+        assert_eq!(convert(&params), "Use GitHub enterprise GitHub");
+
+        let params = params.change_principal(Principal::Cobol);
+        assert_eq!(convert(&params), "USE-GITHUB-ENTERPRISE-GITHUB");
+
+        let params = params.change_principal(Principal::Ada);
+        assert_eq!(convert(&params), "Use_GitHub_Enterprise_GitHub");
+
+        let params = params.change_principal(Principal::CamelSnake);
+        assert_eq!(convert(&params), "use_GitHub_Enterprise_GitHub");
     }
 }
@@ -1,38 +1,162 @@
-use crate::{to_camel, to_constant, to_pascal, to_snake};
+use unicode_xid::UnicodeXID;
+
+use crate::{
+    convert,
+    language::Language,
+    transliteration::transliterate,
+    word::{map_unmasked, unmask},
+    Parameter, PreConvert, Principal,
+};
 
 /// InvalidCharacterCorrector is a struct to correct invalid characters for programming language.
 ///
 /// For example, if you want to convert "invalid:identifier" to snake_case, you can use this struct.
 /// ## Example
 /// ```rust
-/// #[test]
-/// fn test_invalid_character_corrector() {
-///     let source = "invalid:identifier";
-///     let sut = InvalidCharacterCorrector::new();
-///     assert_eq!(sut.to_snake(source), "invalid_identifier");
-/// }
+/// use npc::{corrector::InvalidCharacterCorrector, Principal};
+///
+/// let source = "invalid:identifier";
+/// let sut = InvalidCharacterCorrector::default();
+/// assert_eq!(sut.to_snake(source), "invalid_identifier");
+/// assert_eq!(sut.correct(source, Principal::Chain), "invalid-identifier");
+/// ```
+///
+/// With a language, keywords of the language are escaped.
+/// ```rust
+/// use npc::{corrector::InvalidCharacterCorrector, language::Language};
+///
+/// let mut sut = InvalidCharacterCorrector::default();
+/// sut.change_language(Language::Rust);
+/// assert_eq!(sut.to_snake("type"), "r#type");
+/// ```
+///
+/// With a character set, every character out of the set is invalid too.
+/// ```rust
+/// use npc::corrector::{CharacterSet, InvalidCharacterCorrector};
+///
+/// let mut sut = InvalidCharacterCorrector::default();
+/// sut.change_character_set(CharacterSet::Xid);
+/// assert_eq!(sut.to_snake("user\u{ff1a}name\u{1f600}"), "user_name");
 /// ```
 pub struct InvalidCharacterCorrector {
     invalid_characters: Vec<char>,
+    character_set: CharacterSet,
+    language: Option<Language>,
+}
+
+/// CharacterSet is the set of characters valid for identifiers, checked in addition to the invalid characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CharacterSet {
+    /// only the listed invalid characters are invalid
+    #[default]
+    Listed,
+    /// characters which are not Unicode XID_Continue are invalid, like emoji, NBSP or `：`
+    Xid,
+    /// characters which are not ASCII alphanumeric or `_` are invalid, after letters are transliterated like `é` to `e`
+    Ascii,
+}
+
+impl CharacterSet {
+    fn contains(&self, c: char) -> bool {
+        match self {
+            Self::Listed => true,
+            Self::Xid => c.is_xid_continue(),
+            Self::Ascii => c.is_ascii_alphanumeric() || c == '_',
+        }
+    }
 }
 
 impl InvalidCharacterCorrector {
     pub fn new() -> Self {
         Self {
             invalid_characters: Vec::new(),
+            character_set: CharacterSet::default(),
+            language: None,
         }
     }
+    /// correct replaces invalid characters and converts the source to the principal.
+    ///
+    /// The separator of the principal is not invalid for it, so `-` is kept for chain case.
+    pub fn correct(&self, source: &str, principal: Principal) -> String {
+        self.escape(self.corrected(source, principal))
+    }
     pub fn to_snake(&self, source: &str) -> String {
-        to_snake(&self.replace(source, "_"))
+        self.correct(source, Principal::Snake)
     }
     pub fn to_camel(&self, source: &str) -> String {
-        to_camel(&self.to_snake(source))
+        self.correct(source, Principal::Camel)
     }
     pub fn to_pascal(&self, source: &str) -> String {
-        to_pascal(&self.to_snake(source))
+        self.correct(source, Principal::Pascal)
     }
     pub fn to_constant(&self, source: &str) -> String {
-        to_constant(&self.to_snake(source))
+        self.correct(source, Principal::Constant)
+    }
+    pub fn to_chain(&self, source: &str) -> String {
+        self.correct(source, Principal::Chain)
+    }
+    pub fn to_train(&self, source: &str) -> String {
+        self.correct(source, Principal::Train)
+    }
+    pub fn to_dot(&self, source: &str) -> String {
+        self.correct(source, Principal::Dot)
+    }
+    pub fn to_path(&self, source: &str) -> String {
+        self.correct(source, Principal::Path)
+    }
+    pub fn to_title(&self, source: &str) -> String {
+        self.correct(source, Principal::Title)
+    }
+    pub fn to_sentence(&self, source: &str) -> String {
+        self.correct(source, Principal::Sentence)
+    }
+    /// to_valid_identifier always returns a legal identifier of the language in the principal.
+    ///
+    /// - characters which are not Unicode XID_Continue are invalid whatever the character set is, `user\u{1f600}` -> `user`
+    /// - a separator which can not be in an identifier is replaced with `_`, `user-name` -> `user_name` for chain case
+    /// - a leading character which can not start an identifier, like a digit, is prefixed with `_`, `3d_model` -> `_3d_model`
+    /// - a result without any word falls back to `unnamed`
+    /// - a too long result is truncated with a hash of the whole identifier, like `..._1a2b3c4d`
+    /// - a keyword is escaped, `type` -> `r#type`
+    pub fn to_valid_identifier(
+        &self,
+        source: &str,
+        principal: Principal,
+        language: Language,
+    ) -> String {
+        let xid = Self {
+            invalid_characters: self.invalid_characters.clone(),
+            character_set: match self.character_set {
+                CharacterSet::Listed => CharacterSet::Xid,
+                // ASCII alphanumeric and `_` are in XID already
+                character_set => character_set,
+            },
+            language: None,
+        };
+        let mut result = xid
+            .corrected(source, principal)
+            .replace(|c: char| !c.is_xid_continue(), "_");
+        if !result.chars().any(char::is_alphanumeric) {
+            result = convert(&Parameter::new(FALLBACK_IDENTIFIER, principal));
+        }
+        if result.starts_with(|c: char| !(c.is_xid_start() || c == '_')) {
+            result.insert(0, '_');
+        }
+        if let Some(max_length) = language.max_length() {
+            result = truncate(result, max_length);
+        }
+        language.escape(&result)
+    }
+    /// change_language makes the corrector escape keywords of the language, like `r#type` for Rust
+    pub fn change_language(&mut self, language: Language) {
+        self.language = Some(language);
+    }
+    /// change_character_set makes characters out of the set invalid, the listed invalid characters are still invalid
+    pub fn change_character_set(&mut self, character_set: CharacterSet) {
+        self.character_set = character_set;
+    }
+    pub fn to_convertor(self) -> Box<Self> {
+        Box::new(self)
     }
     pub fn add_invalid_character(&mut self, character: char) {
         self.invalid_characters.push(character);
@@ -40,12 +164,70 @@ impl InvalidCharacterCorrector {
     pub fn remove_invalid_character(&mut self, character: char) {
         self.invalid_characters.retain(|c| c != &character);
     }
-    fn replace(&self, source: &str, target: &str) -> String {
-        let mut result = source.replace(|c| self.invalid_characters.contains(&c), target);
-        if result.get(result.len() - target.len()..result.len()) == Some(target) {
-            result.pop();
+    fn corrected(&self, source: &str, principal: Principal) -> String {
+        convert(&Parameter::new(
+            self.replace(&unmask(source), principal),
+            principal,
+        ))
+    }
+    fn escape(&self, identifier: String) -> String {
+        match self.language {
+            Some(language) => language.escape(&identifier),
+            None => identifier,
         }
-        result
+    }
+    fn is_invalid(&self, c: char) -> bool {
+        self.invalid_characters.contains(&c) || !self.character_set.contains(c)
+    }
+    // replace invalid characters with the separator of the principal, or `_` if it has no separator,
+    // and trim the separators at the head and the tail like `TrimConverter`,
+    // the words masked by a pre-converter like `IgnoreWordsConverter` are kept
+    fn replace(&self, source: &str, principal: Principal) -> String {
+        let target = principal.separator().unwrap_or('_');
+        let result = map_unmasked(source, |part| {
+            let part = match self.character_set {
+                CharacterSet::Ascii => transliterate(part, false),
+                _ => part.to_string(),
+            };
+            part.replace(|c| c != target && self.is_invalid(c), &target.to_string())
+        });
+        result.trim_matches(target).to_string()
+    }
+}
+
+const FALLBACK_IDENTIFIER: &str = "unnamed";
+
+// truncate the identifier to max_length bytes with the hash of the whole identifier,
+// so different long identifiers stay different after truncation
+fn truncate(identifier: String, max_length: usize) -> String {
+    if identifier.len() <= max_length {
+        return identifier;
+    }
+    let hash = format!("{:08x}", fnv1a(&identifier));
+    let hash = if identifier.chars().any(char::is_lowercase) {
+        hash
+    } else {
+        hash.to_ascii_uppercase()
+    };
+    let mut end = max_length.saturating_sub(hash.len() + 1);
+    while !identifier.is_char_boundary(end) {
+        end -= 1;
+    }
+    let head = identifier[..end].trim_end_matches(|c: char| !c.is_alphanumeric());
+    format!("{}_{}", head, hash)
+}
+
+// 32 bit FNV-1a, which is stable across versions and platforms unlike DefaultHasher
+fn fnv1a(source: &str) -> u32 {
+    source.bytes().fold(0x811c9dc5, |hash, b| {
+        (hash ^ b as u32).wrapping_mul(0x01000193)
+    })
+}
+
+// keywords are not escaped here because the result is converted again
+impl PreConvert for InvalidCharacterCorrector {
+    fn convert(&self, source: &str, principal: Principal) -> String {
+        self.replace(source, principal)
     }
 }
 impl Default for InvalidCharacterCorrector {
@@ -55,6 +237,8 @@ impl Default for InvalidCharacterCorrector {
                 ':', ' ', '-', '/', '\\', '.', ',', ';', '\'', '"', '[', ']', '{', '}', '(', ')',
                 '<', '>', '?', '!', '@', '#', '$', '%', '^', '&', '*', '+', '=', '|', '~', '`',
             ],
+            character_set: CharacterSet::default(),
+            language: None,
         }
     }
 }
@@ -89,5 +273,185 @@ mod tests {
         assert_eq!(sut.to_camel(source), "invalidIdentifier");
         assert_eq!(sut.to_pascal(source), "InvalidIdentifier");
         assert_eq!(sut.to_constant(source), "INVALID_IDENTIFIER");
+        assert_eq!(sut.to_train(source), "Invalid-Identifier");
+        assert_eq!(sut.to_dot(source), "invalid.identifier");
+        assert_eq!(sut.to_path(source), "invalid/identifier");
+        assert_eq!(sut.to_title(source), "Invalid Identifier");
+        assert_eq!(sut.to_sentence(source), "Invalid identifier");
+        // separators left at the edges are trimmed
+        assert_eq!(sut.correct("---", Principal::Chain), "");
+        assert_eq!(sut.correct("@@@", Principal::Title), "");
+        assert_eq!(sut.correct("@@@", Principal::Snake), "");
+        assert_eq!(sut.correct("@-user-name-@@", Principal::Chain), "user-name");
+        assert_eq!(sut.correct("  user name  ", Principal::Title), "User Name");
+    }
+    #[test]
+    fn 言語のキーワードになる識別子はエスケープされる() {
+        let mut sut = InvalidCharacterCorrector::default();
+        sut.change_language(Language::Rust);
+        assert_eq!(sut.to_snake("type"), "r#type");
+        assert_eq!(sut.to_pascal("self"), "Self_");
+        assert_eq!(sut.to_snake("user:type"), "user_type");
+        sut.change_language(Language::Python);
+        assert_eq!(sut.to_snake("import"), "import_");
+        assert_eq!(sut.to_pascal("none"), "None_");
+        sut.change_language(Language::CSharp);
+        assert_eq!(sut.to_camel("class"), "@class");
+        sut.change_language(Language::Sql);
+        assert_eq!(sut.to_constant("user"), "\"USER\"");
+    }
+    #[test]
+    fn どんな入力からも言語にとって正しい識別子を作る() {
+        let sut = InvalidCharacterCorrector::default();
+        assert_eq!(
+            sut.to_valid_identifier("3d model", Principal::Snake, Language::Rust),
+            "_3d_model"
+        );
+        assert_eq!(
+            sut.to_valid_identifier("3d model", Principal::Pascal, Language::Go),
+            "_3dModel"
+        );
+        // empty input does not panic
+        assert_eq!(sut.to_snake(""), "");
+        assert_eq!(
+            sut.to_valid_identifier("", Principal::Pascal, Language::Rust),
+            "Unnamed"
+        );
+        assert_eq!(
+            sut.to_valid_identifier("!?", Principal::Constant, Language::Rust),
+            "UNNAMED"
+        );
+        assert_eq!(
+            sut.to_valid_identifier("type", Principal::Snake, Language::Rust),
+            "r#type"
+        );
+        assert_eq!(
+            sut.to_valid_identifier("user", Principal::Snake, Language::Sql),
+            "\"user\""
+        );
+
+        let long =
+            "a very long column name which is longer than the limit of postgresql identifiers";
+        let result = sut.to_valid_identifier(long, Principal::Snake, Language::Sql);
+        assert_eq!(result.len(), 63);
+        assert!(result.starts_with("a_very_long_column_name_which_is_longer_than_the_limit_"));
+        assert_eq!(
+            result,
+            sut.to_valid_identifier(long, Principal::Snake, Language::Sql)
+        );
+        let other =
+            sut.to_valid_identifier(&format!("{} too", long), Principal::Snake, Language::Sql);
+        assert_ne!(result, other);
+        assert_eq!(other.len(), 63);
+        let result = sut.to_valid_identifier(long, Principal::Constant, Language::Sql);
+        assert!(!result.chars().any(char::is_lowercase));
+    }
+    #[test]
+    fn 文字集合や命名規則によらずxidの識別子を作る() {
+        let is_identifier = |identifier: &str| {
+            let mut chars = identifier.chars();
+            chars.next().is_some_and(|c| c.is_xid_start() || c == '_')
+                && chars.all(|c| c.is_xid_continue())
+        };
+        let principals = [
+            Principal::Camel,
+            Principal::Pascal,
+            Principal::Snake,
+            Principal::Constant,
+            Principal::Chain,
+            Principal::Train,
+            Principal::Dot,
+            Principal::Path,
+            Principal::Title,
+            Principal::Sentence,
+            Principal::Flat,
+            Principal::UpperFlat,
+            Principal::Cobol,
+            Principal::Ada,
+            Principal::CamelSnake,
+        ];
+        let sut = InvalidCharacterCorrector::default();
+        for source in [
+            "\u{1f600}abc",
+            "user name",
+            "-user",
+            "user\u{a0}name\u{1f600}",
+            "\u{1f600}",
+        ] {
+            for principal in principals {
+                let result = sut.to_valid_identifier(source, principal, Language::Java);
+                assert!(is_identifier(&result), "{} {:?}", result, principal);
+            }
+        }
+        assert_eq!(
+            sut.to_valid_identifier("\u{1f600}abc", Principal::Snake, Language::Java),
+            "abc"
+        );
+        assert_eq!(
+            sut.to_valid_identifier("user\u{a0}name\u{1f600}", Principal::Camel, Language::Java),
+            "userName"
+        );
+        assert_eq!(
+            sut.to_valid_identifier("-user", Principal::Chain, Language::Java),
+            "user"
+        );
+        let expected = [
+            (Principal::Chain, "user_name"),
+            (Principal::Train, "User_Name"),
+            (Principal::Dot, "user_name"),
+            (Principal::Path, "user_name"),
+            (Principal::Title, "User_Name"),
+            (Principal::Sentence, "User_name"),
+            (Principal::Cobol, "USER_NAME"),
+        ];
+        for (principal, identifier) in expected {
+            assert_eq!(
+                sut.to_valid_identifier("user name", principal, Language::Java),
+                identifier
+            );
+        }
+        // the correction out of to_valid_identifier keeps the separator
+        assert_eq!(sut.correct("user name", Principal::Chain), "user-name");
+    }
+    #[test]
+    fn 識別子に使えない文字をユニコードのxidで判定する() {
+        let mut sut = InvalidCharacterCorrector::default();
+        sut.change_character_set(CharacterSet::Xid);
+        assert_eq!(sut.to_snake("user\u{1f600}name"), "user_name");
+        assert_eq!(sut.to_snake("user\u{a0}name"), "user_name");
+        assert_eq!(sut.to_snake("user\u{200b}name"), "user_name");
+        assert_eq!(sut.to_snake("関数（引数）"), "関数_引数");
+        assert_eq!(sut.to_snake("café_crème"), "café_crème");
+        // the listed characters are still invalid
+        assert_eq!(sut.to_snake("user:name"), "user_name");
+        // private use characters in the source do not mask a part of it
+        assert_eq!(sut.to_snake("\u{E000}user:name\u{E001}"), "user_name");
+        // a combining mark is XID_Continue but can not start an identifier
+        assert_eq!(
+            sut.to_valid_identifier("\u{0301}name", Principal::Snake, Language::Rust),
+            "_\u{0301}name"
+        );
+
+        let mut sut = InvalidCharacterCorrector::new();
+        sut.change_character_set(CharacterSet::Ascii);
+        assert_eq!(sut.to_snake("user\u{1f600}name"), "user_name");
+        assert_eq!(sut.to_snake("café_Straße"), "cafe_strasse");
+    }
+    #[test]
+    fn 変換先の区切り文字は不正な文字として扱わない() {
+        let sut = InvalidCharacterCorrector::default();
+        assert_eq!(sut.to_chain("invalid:identifier@"), "invalid-identifier");
+        assert_eq!(sut.to_chain("user-name"), "user-name");
+        assert_eq!(
+            sut.correct("user-name:id", Principal::Cobol),
+            "USER-NAME-ID"
+        );
+        assert_eq!(sut.correct("userName.id", Principal::Dot), "user.name.id");
+        assert_eq!(sut.correct("user(name)", Principal::Title), "User Name");
+
+        let param = Parameter::new("user-name@host", Principal::Chain)
+            .add_pre_convert(InvalidCharacterCorrector::default().to_convertor());
+        assert_eq!(convert(&param), "user-name-host");
+        assert_eq!(sut.convert("userName:id", Principal::Chain), "userName-id");
     }
 }
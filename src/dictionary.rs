@@ -0,0 +1,207 @@
+/// Dictionary is a bundled list of well-known identifiers of a domain with their canonical casing.
+///
+/// Each dictionary is enabled by its cargo feature, `dict-cloud`, `dict-lang`, `dict-db` and `dict-os`.
+/// ## Example
+/// ```rust
+/// use npc::{convert, convertor::PascalCaseReservedIdentifiersConverter, dictionary::Dictionary};
+/// use npc::{Parameter, Principal};
+///
+/// // `Dictionary::Db` if `dict-db` is enabled
+/// if let Some(db) = Dictionary::from_name("db") {
+///     let reserved = PascalCaseReservedIdentifiersConverter::default().add_dictionary(db);
+///     let param = Parameter::new("postgresql_client", Principal::Pascal)
+///         .add_post_convert(reserved.to_convertor());
+///     assert_eq!(convert(&param), "PostgreSQLClient");
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dictionary {
+    /// cloud vendors and services, like `AWS` and `BigQuery`
+    #[cfg(feature = "dict-cloud")]
+    Cloud,
+    /// programming languages and runtimes, like `JavaScript` and `GraphQL`
+    #[cfg(feature = "dict-lang")]
+    Lang,
+    /// databases, like `PostgreSQL` and `MongoDB`
+    #[cfg(feature = "dict-db")]
+    Db,
+    /// operating systems and devices, like `iOS` and `macOS`
+    #[cfg(feature = "dict-os")]
+    Os,
+}
+
+impl Dictionary {
+    /// every dictionary enabled by the features
+    pub fn all() -> &'static [Self] {
+        &[
+            #[cfg(feature = "dict-cloud")]
+            Self::Cloud,
+            #[cfg(feature = "dict-lang")]
+            Self::Lang,
+            #[cfg(feature = "dict-db")]
+            Self::Db,
+            #[cfg(feature = "dict-os")]
+            Self::Os,
+        ]
+    }
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::all().iter().copied().find(|d| d.name() == name)
+    }
+    pub fn name(&self) -> &'static str {
+        match *self {
+            #[cfg(feature = "dict-cloud")]
+            Self::Cloud => "cloud",
+            #[cfg(feature = "dict-lang")]
+            Self::Lang => "lang",
+            #[cfg(feature = "dict-db")]
+            Self::Db => "db",
+            #[cfg(feature = "dict-os")]
+            Self::Os => "os",
+        }
+    }
+    pub fn words(&self) -> &'static [&'static str] {
+        match *self {
+            #[cfg(feature = "dict-cloud")]
+            Self::Cloud => CLOUD,
+            #[cfg(feature = "dict-lang")]
+            Self::Lang => LANG,
+            #[cfg(feature = "dict-db")]
+            Self::Db => DB,
+            #[cfg(feature = "dict-os")]
+            Self::Os => OS,
+        }
+    }
+}
+
+#[cfg(feature = "dict-cloud")]
+const CLOUD: &[&str] = &[
+    "AWS",
+    "GCP",
+    "IAM",
+    "EC2",
+    "S3",
+    "SQS",
+    "SNS",
+    "ECS",
+    "EKS",
+    "GKE",
+    "AKS",
+    "DynamoDB",
+    "CloudFront",
+    "CloudWatch",
+    "CloudFormation",
+    "BigQuery",
+    "BigTable",
+    "PubSub",
+    "CosmosDB",
+    "DigitalOcean",
+    "OpenStack",
+];
+
+#[cfg(feature = "dict-lang")]
+const LANG: &[&str] = &[
+    "JavaScript",
+    "TypeScript",
+    "CoffeeScript",
+    "ActionScript",
+    "PowerShell",
+    "WebAssembly",
+    "GraphQL",
+    "OCaml",
+    "PHP",
+    "CPython",
+    "PyPy",
+    "JRuby",
+    "LuaJIT",
+    "NodeJS",
+];
+
+#[cfg(feature = "dict-db")]
+const DB: &[&str] = &[
+    "PostgreSQL",
+    "MySQL",
+    "MariaDB",
+    "SQLite",
+    "MongoDB",
+    "CouchDB",
+    "CockroachDB",
+    "ClickHouse",
+    "InfluxDB",
+    "DuckDB",
+    "TiDB",
+    "ScyllaDB",
+    "DynamoDB",
+    "NoSQL",
+    "MSSQL",
+];
+
+#[cfg(feature = "dict-os")]
+const OS: &[&str] = &[
+    "iOS", "iPadOS", "macOS", "watchOS", "tvOS", "visionOS", "iPhone", "iPad", "ChromeOS",
+    "FreeBSD", "OpenBSD", "NetBSD",
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_dictionary_has_name_and_words() {
+        for dictionary in Dictionary::all() {
+            assert_eq!(Dictionary::from_name(dictionary.name()), Some(*dictionary));
+            assert!(!dictionary.words().is_empty());
+        }
+        assert_eq!(Dictionary::from_name("unknown"), None);
+    }
+    #[cfg(all(feature = "dict-cloud", feature = "dict-db", feature = "dict-os"))]
+    #[test]
+    fn bundled_dictionaries_work_with_every_principal() {
+        use crate::{
+            convert, convertor::PascalCaseReservedIdentifiersConverter, Parameter, Principal,
+        };
+        let reserved = || {
+            PascalCaseReservedIdentifiersConverter::default()
+                .add_dictionary(Dictionary::Cloud)
+                .add_dictionary(Dictionary::Db)
+                .add_dictionary(Dictionary::Os)
+                .to_convertor()
+        };
+        let param = |to: Principal| {
+            Parameter::new("aws_bigquery_to_postgresql", to).add_post_convert(reserved())
+        };
+        assert_eq!(
+            convert(&param(Principal::Pascal)),
+            "AWSBigQueryToPostgreSQL"
+        );
+        assert_eq!(convert(&param(Principal::Camel)), "awsBigQueryToPostgreSQL");
+        assert_eq!(
+            convert(&param(Principal::Snake)),
+            "aws_bigquery_to_postgresql"
+        );
+        assert_eq!(
+            convert(&param(Principal::Constant)),
+            "AWS_BIGQUERY_TO_POSTGRESQL"
+        );
+        assert_eq!(
+            convert(&param(Principal::Title)),
+            "AWS BigQuery To PostgreSQL"
+        );
+        assert_eq!(
+            convert(&param(Principal::Sentence)),
+            "AWS BigQuery to PostgreSQL"
+        );
+        // separated words are not joined into a dictionary word
+        let param = Parameter::new("i_am_here", Principal::Pascal).add_post_convert(reserved());
+        assert_eq!(convert(&param), "IAmHere");
+        // camel case words are not joined into an upper case dictionary word
+        let param = Parameter::new("iAmRole", Principal::Camel).add_post_convert(reserved());
+        assert_eq!(convert(&param), "iAmRole");
+        let param = param.change_principal(Principal::Pascal);
+        assert_eq!(convert(&param), "IAmRole");
+        let param = param.change_source("iamRole");
+        assert_eq!(convert(&param), "IAMRole");
+        // but they are joined into a mixed case one
+        let param = param.change_source("bigQueryTable");
+        assert_eq!(convert(&param), "BigQueryTable");
+    }
+}
@@ -0,0 +1,43 @@
+use std::fmt::Display;
+
+use crate::Principal;
+
+/// NpcError is returned by the fallible APIs like `try_convert` and `NamingPrincipalConvertor::try_new`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NpcError {
+    /// the source is empty
+    EmptyInput,
+    /// the source has no word to convert, like `"__"` or `"!?"`
+    UnsupportedInput(String),
+    /// the source has caseless letters, like `"ユーザーID"`, and `Caseless::Reject` is set
+    CaselessInput(String),
+    /// the converted result is not a valid identifier of the principal
+    InvalidIdentifier {
+        converted: String,
+        principal: Principal,
+    },
+}
+
+impl Display for NpcError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::EmptyInput => write!(f, "input is empty"),
+            Self::UnsupportedInput(source) => {
+                write!(f, "input {:?} has no word to convert", source)
+            }
+            Self::CaselessInput(source) => {
+                write!(f, "input {:?} has caseless characters", source)
+            }
+            Self::InvalidIdentifier {
+                converted,
+                principal,
+            } => write!(
+                f,
+                "converted {:?} is not a valid {:?} case identifier",
+                converted, principal
+            ),
+        }
+    }
+}
+
+impl std::error::Error for NpcError {}
@@ -0,0 +1,961 @@
+/// Language is a programming language whose keywords can not be used as identifiers as they are.
+///
+/// ## Example
+/// ```rust
+/// use npc::language::Language;
+///
+/// assert_eq!(Language::Rust.escape("type"), "r#type");
+/// assert_eq!(Language::Python.escape("class"), "class_");
+/// assert_eq!(Language::CSharp.escape("class"), "@class");
+/// assert_eq!(Language::Go.escape("user_type"), "user_type");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    Rust,
+    Python,
+    TypeScript,
+    Go,
+    Java,
+    CSharp,
+    Kotlin,
+    Swift,
+    Sql,
+}
+
+impl Language {
+    pub fn all() -> &'static [Self] {
+        &[
+            Self::Rust,
+            Self::Python,
+            Self::TypeScript,
+            Self::Go,
+            Self::Java,
+            Self::CSharp,
+            Self::Kotlin,
+            Self::Swift,
+            Self::Sql,
+        ]
+    }
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::all().iter().copied().find(|l| l.name() == name)
+    }
+    /// the language of the file extension like `rs`, None if it is unknown
+    ///
+    /// JavaScript is read as TypeScript.
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        let language = match extension.to_ascii_lowercase().as_str() {
+            "rs" => Self::Rust,
+            "py" | "pyi" => Self::Python,
+            "ts" | "tsx" | "mts" | "cts" | "js" | "jsx" | "mjs" | "cjs" => Self::TypeScript,
+            "go" => Self::Go,
+            "java" => Self::Java,
+            "cs" => Self::CSharp,
+            "kt" | "kts" => Self::Kotlin,
+            "swift" => Self::Swift,
+            "sql" => Self::Sql,
+            _ => return None,
+        };
+        Some(language)
+    }
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Rust => "rust",
+            Self::Python => "python",
+            Self::TypeScript => "typescript",
+            Self::Go => "go",
+            Self::Java => "java",
+            Self::CSharp => "csharp",
+            Self::Kotlin => "kotlin",
+            Self::Swift => "swift",
+            Self::Sql => "sql",
+        }
+    }
+    /// reserved keywords of the language
+    pub fn keywords(&self) -> &'static [&'static str] {
+        match self {
+            Self::Rust => RUST,
+            Self::Python => PYTHON,
+            Self::TypeScript => TYPESCRIPT,
+            Self::Go => GO,
+            Self::Java => JAVA,
+            Self::CSharp => CSHARP,
+            Self::Kotlin => KOTLIN,
+            Self::Swift => SWIFT,
+            Self::Sql => SQL,
+        }
+    }
+    /// whether the identifier is a keyword, keywords of SQL are case insensitive
+    pub fn is_keyword(&self, identifier: &str) -> bool {
+        match self {
+            Self::Sql => self
+                .keywords()
+                .iter()
+                .any(|k| k.eq_ignore_ascii_case(identifier)),
+            _ => self.keywords().contains(&identifier),
+        }
+    }
+    /// whether `rewrite_text` keeps the identifier as it is
+    ///
+    /// Contextual keywords like `var` of C# and predeclared names like `int` of Go are kept too,
+    /// but they are not escaped because they are usable as identifiers.
+    pub fn is_preserved(&self, identifier: &str) -> bool {
+        let preserved = match self {
+            Self::Rust => RUST_PRESERVED,
+            Self::Python => PYTHON_PRESERVED,
+            Self::TypeScript => TYPESCRIPT_PRESERVED,
+            Self::Go => GO_PRESERVED,
+            Self::Java => JAVA_PRESERVED,
+            Self::CSharp => CSHARP_PRESERVED,
+            Self::Kotlin => KOTLIN_PRESERVED,
+            Self::Swift => SWIFT_PRESERVED,
+            Self::Sql => SQL_PRESERVED,
+        };
+        self.is_keyword(identifier)
+            || match self {
+                Self::Sql => preserved.iter().any(|w| w.eq_ignore_ascii_case(identifier)),
+                _ => preserved.contains(&identifier),
+            }
+    }
+    /// max_length is the limit of an identifier in bytes, None if the language has no practical limit
+    ///
+    /// SQL is limited to 63 bytes like PostgreSQL.
+    pub fn max_length(&self) -> Option<usize> {
+        match self {
+            Self::Sql => Some(63),
+            _ => None,
+        }
+    }
+    /// markers of comments which continue to the end of the line
+    pub fn line_comments(&self) -> &'static [&'static str] {
+        match self {
+            Self::Python => &["#"],
+            Self::Sql => &["--"],
+            _ => &["//"],
+        }
+    }
+    /// markers of the start and the end of block comments, None if the language has no block comment
+    pub fn block_comment(&self) -> Option<(&'static str, &'static str)> {
+        match self {
+            Self::Python => None,
+            _ => Some(("/*", "*/")),
+        }
+    }
+    /// whether block comments can be nested, like `/* a /* b */ c */` of Rust
+    pub fn nests_block_comments(&self) -> bool {
+        matches!(self, Self::Rust | Self::Kotlin | Self::Swift)
+    }
+    /// escape returns the identifier which is usable in the language if it is a keyword
+    ///
+    /// - Rust: `r#type`, or `self_` for the keywords which can not be raw identifiers
+    /// - Python, TypeScript, Go and Java: `type_`
+    /// - C#: `@class`
+    /// - Kotlin and Swift: `` `class` ``
+    /// - SQL: `"select"`
+    pub fn escape(&self, identifier: &str) -> String {
+        if !self.is_keyword(identifier) {
+            return identifier.to_string();
+        }
+        match self {
+            Self::Rust if matches!(identifier, "crate" | "self" | "Self" | "super") => {
+                format!("{}_", identifier)
+            }
+            Self::Rust => format!("r#{}", identifier),
+            Self::Python | Self::TypeScript | Self::Go | Self::Java => {
+                format!("{}_", identifier)
+            }
+            Self::CSharp => format!("@{}", identifier),
+            Self::Kotlin | Self::Swift => format!("`{}`", identifier),
+            Self::Sql => format!("\"{}\"", identifier),
+        }
+    }
+}
+
+const RUST: &[&str] = &[
+    "as", "break", "const", "continue", "crate", "else", "enum", "extern", "false", "fn", "for",
+    "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return",
+    "self", "Self", "static", "struct", "super", "trait", "true", "type", "unsafe", "use", "where",
+    "while", "async", "await", "dyn", "abstract", "become", "box", "do", "final", "macro",
+    "override", "priv", "typeof", "unsized", "virtual", "yield", "try", "gen",
+];
+
+const PYTHON: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield", "match", "case", "type",
+];
+
+const TYPESCRIPT: &[&str] = &[
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "debugger",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "enum",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "for",
+    "function",
+    "if",
+    "import",
+    "in",
+    "instanceof",
+    "new",
+    "null",
+    "return",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "var",
+    "void",
+    "while",
+    "with",
+    "implements",
+    "interface",
+    "let",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "static",
+    "yield",
+];
+
+const GO: &[&str] = &[
+    "break",
+    "case",
+    "chan",
+    "const",
+    "continue",
+    "default",
+    "defer",
+    "else",
+    "fallthrough",
+    "for",
+    "func",
+    "go",
+    "goto",
+    "if",
+    "import",
+    "interface",
+    "map",
+    "package",
+    "range",
+    "return",
+    "select",
+    "struct",
+    "switch",
+    "type",
+    "var",
+];
+
+const JAVA: &[&str] = &[
+    "abstract",
+    "assert",
+    "boolean",
+    "break",
+    "byte",
+    "case",
+    "catch",
+    "char",
+    "class",
+    "const",
+    "continue",
+    "default",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "extends",
+    "final",
+    "finally",
+    "float",
+    "for",
+    "goto",
+    "if",
+    "implements",
+    "import",
+    "instanceof",
+    "int",
+    "interface",
+    "long",
+    "native",
+    "new",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "short",
+    "static",
+    "strictfp",
+    "super",
+    "switch",
+    "synchronized",
+    "this",
+    "throw",
+    "throws",
+    "transient",
+    "try",
+    "void",
+    "volatile",
+    "while",
+    "true",
+    "false",
+    "null",
+];
+
+const CSHARP: &[&str] = &[
+    "abstract",
+    "as",
+    "base",
+    "bool",
+    "break",
+    "byte",
+    "case",
+    "catch",
+    "char",
+    "checked",
+    "class",
+    "const",
+    "continue",
+    "decimal",
+    "default",
+    "delegate",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "event",
+    "explicit",
+    "extern",
+    "false",
+    "finally",
+    "fixed",
+    "float",
+    "for",
+    "foreach",
+    "goto",
+    "if",
+    "implicit",
+    "in",
+    "int",
+    "interface",
+    "internal",
+    "is",
+    "lock",
+    "long",
+    "namespace",
+    "new",
+    "null",
+    "object",
+    "operator",
+    "out",
+    "override",
+    "params",
+    "private",
+    "protected",
+    "public",
+    "readonly",
+    "ref",
+    "return",
+    "sbyte",
+    "sealed",
+    "short",
+    "sizeof",
+    "stackalloc",
+    "static",
+    "string",
+    "struct",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "uint",
+    "ulong",
+    "unchecked",
+    "unsafe",
+    "ushort",
+    "using",
+    "virtual",
+    "void",
+    "volatile",
+    "while",
+];
+
+const KOTLIN: &[&str] = &[
+    "as",
+    "break",
+    "class",
+    "continue",
+    "do",
+    "else",
+    "false",
+    "for",
+    "fun",
+    "if",
+    "in",
+    "interface",
+    "is",
+    "null",
+    "object",
+    "package",
+    "return",
+    "super",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typealias",
+    "typeof",
+    "val",
+    "var",
+    "when",
+    "while",
+];
+
+const SWIFT: &[&str] = &[
+    "associatedtype",
+    "class",
+    "deinit",
+    "enum",
+    "extension",
+    "fileprivate",
+    "func",
+    "import",
+    "init",
+    "inout",
+    "internal",
+    "let",
+    "open",
+    "operator",
+    "private",
+    "precedencegroup",
+    "protocol",
+    "public",
+    "rethrows",
+    "static",
+    "struct",
+    "subscript",
+    "typealias",
+    "var",
+    "break",
+    "case",
+    "catch",
+    "continue",
+    "default",
+    "defer",
+    "do",
+    "else",
+    "fallthrough",
+    "for",
+    "guard",
+    "if",
+    "in",
+    "repeat",
+    "return",
+    "throw",
+    "switch",
+    "where",
+    "while",
+    "Any",
+    "as",
+    "await",
+    "false",
+    "is",
+    "nil",
+    "self",
+    "Self",
+    "super",
+    "throws",
+    "true",
+    "try",
+];
+
+const SQL: &[&str] = &[
+    "all",
+    "alter",
+    "and",
+    "as",
+    "asc",
+    "begin",
+    "between",
+    "by",
+    "case",
+    "check",
+    "column",
+    "commit",
+    "constraint",
+    "create",
+    "default",
+    "delete",
+    "desc",
+    "distinct",
+    "drop",
+    "else",
+    "end",
+    "exists",
+    "foreign",
+    "from",
+    "full",
+    "grant",
+    "group",
+    "having",
+    "in",
+    "index",
+    "inner",
+    "insert",
+    "into",
+    "is",
+    "join",
+    "key",
+    "left",
+    "like",
+    "limit",
+    "not",
+    "null",
+    "offset",
+    "on",
+    "or",
+    "order",
+    "outer",
+    "primary",
+    "references",
+    "revoke",
+    "right",
+    "rollback",
+    "select",
+    "table",
+    "then",
+    "to",
+    "union",
+    "unique",
+    "update",
+    "user",
+    "values",
+    "view",
+    "when",
+    "where",
+    "with",
+];
+
+// contextual keywords and predeclared names which are kept by `rewrite_text` but not escaped
+
+const RUST_PRESERVED: &[&str] = &[
+    "union",
+    "macro_rules",
+    "raw",
+    "safe",
+    "bool",
+    "char",
+    "str",
+    "i8",
+    "i16",
+    "i32",
+    "i64",
+    "i128",
+    "isize",
+    "u8",
+    "u16",
+    "u32",
+    "u64",
+    "u128",
+    "usize",
+    "f32",
+    "f64",
+    "Option",
+    "Some",
+    "None",
+    "Result",
+    "Ok",
+    "Err",
+    "String",
+    "Vec",
+    "Box",
+];
+
+const PYTHON_PRESERVED: &[&str] = &[
+    "self",
+    "cls",
+    "print",
+    "len",
+    "range",
+    "int",
+    "float",
+    "str",
+    "bool",
+    "bytes",
+    "list",
+    "dict",
+    "set",
+    "tuple",
+    "object",
+    "super",
+    "isinstance",
+    "enumerate",
+    "zip",
+    "open",
+];
+
+const TYPESCRIPT_PRESERVED: &[&str] = &[
+    "abstract",
+    "accessor",
+    "any",
+    "as",
+    "asserts",
+    "async",
+    "await",
+    "bigint",
+    "boolean",
+    "constructor",
+    "declare",
+    "from",
+    "get",
+    "infer",
+    "is",
+    "keyof",
+    "module",
+    "namespace",
+    "never",
+    "number",
+    "object",
+    "of",
+    "override",
+    "readonly",
+    "require",
+    "satisfies",
+    "set",
+    "string",
+    "symbol",
+    "type",
+    "undefined",
+    "unique",
+    "unknown",
+    "console",
+    "Array",
+    "Boolean",
+    "Error",
+    "Map",
+    "Number",
+    "Object",
+    "Promise",
+    "Set",
+    "String",
+];
+
+const GO_PRESERVED: &[&str] = &[
+    "any",
+    "bool",
+    "byte",
+    "comparable",
+    "complex64",
+    "complex128",
+    "error",
+    "float32",
+    "float64",
+    "int",
+    "int8",
+    "int16",
+    "int32",
+    "int64",
+    "rune",
+    "string",
+    "uint",
+    "uint8",
+    "uint16",
+    "uint32",
+    "uint64",
+    "uintptr",
+    "true",
+    "false",
+    "iota",
+    "nil",
+    "append",
+    "cap",
+    "clear",
+    "close",
+    "complex",
+    "copy",
+    "delete",
+    "imag",
+    "len",
+    "make",
+    "max",
+    "min",
+    "new",
+    "panic",
+    "print",
+    "println",
+    "real",
+    "recover",
+];
+
+const JAVA_PRESERVED: &[&str] = &[
+    "var",
+    "record",
+    "yield",
+    "sealed",
+    "permits",
+    "when",
+    "module",
+    "open",
+    "requires",
+    "exports",
+    "opens",
+    "to",
+    "uses",
+    "provides",
+    "with",
+    "transitive",
+    "Boolean",
+    "Double",
+    "Integer",
+    "Long",
+    "Object",
+    "Override",
+    "String",
+    "System",
+];
+
+const CSHARP_PRESERVED: &[&str] = &[
+    "add",
+    "alias",
+    "and",
+    "ascending",
+    "args",
+    "async",
+    "await",
+    "by",
+    "descending",
+    "dynamic",
+    "equals",
+    "file",
+    "from",
+    "get",
+    "global",
+    "group",
+    "init",
+    "into",
+    "join",
+    "let",
+    "managed",
+    "nameof",
+    "nint",
+    "not",
+    "notnull",
+    "nuint",
+    "on",
+    "or",
+    "orderby",
+    "partial",
+    "record",
+    "remove",
+    "required",
+    "scoped",
+    "select",
+    "set",
+    "unmanaged",
+    "value",
+    "var",
+    "when",
+    "where",
+    "with",
+    "yield",
+    "Console",
+    "Object",
+    "String",
+    "Task",
+];
+
+const KOTLIN_PRESERVED: &[&str] = &[
+    "by",
+    "catch",
+    "constructor",
+    "delegate",
+    "dynamic",
+    "field",
+    "file",
+    "finally",
+    "get",
+    "import",
+    "init",
+    "param",
+    "property",
+    "receiver",
+    "set",
+    "setparam",
+    "value",
+    "where",
+    "abstract",
+    "actual",
+    "annotation",
+    "companion",
+    "const",
+    "crossinline",
+    "data",
+    "enum",
+    "expect",
+    "external",
+    "final",
+    "infix",
+    "inline",
+    "inner",
+    "internal",
+    "lateinit",
+    "noinline",
+    "open",
+    "operator",
+    "out",
+    "override",
+    "private",
+    "protected",
+    "public",
+    "reified",
+    "sealed",
+    "suspend",
+    "tailrec",
+    "vararg",
+    "it",
+    "Any",
+    "Boolean",
+    "Double",
+    "Int",
+    "Long",
+    "Nothing",
+    "String",
+    "Unit",
+    "listOf",
+    "mapOf",
+    "println",
+    "setOf",
+];
+
+const SWIFT_PRESERVED: &[&str] = &[
+    "actor",
+    "any",
+    "associativity",
+    "async",
+    "borrowing",
+    "consuming",
+    "convenience",
+    "didSet",
+    "dynamic",
+    "final",
+    "get",
+    "indirect",
+    "infix",
+    "isolated",
+    "lazy",
+    "left",
+    "mutating",
+    "none",
+    "nonisolated",
+    "nonmutating",
+    "optional",
+    "override",
+    "package",
+    "postfix",
+    "precedence",
+    "prefix",
+    "Protocol",
+    "required",
+    "right",
+    "set",
+    "some",
+    "Type",
+    "unowned",
+    "weak",
+    "willSet",
+    "Array",
+    "Bool",
+    "Dictionary",
+    "Double",
+    "Float",
+    "Int",
+    "Optional",
+    "String",
+    "print",
+];
+
+const SQL_PRESERVED: &[&str] = &[
+    "avg",
+    "bigint",
+    "boolean",
+    "coalesce",
+    "count",
+    "current_timestamp",
+    "date",
+    "integer",
+    "max",
+    "min",
+    "now",
+    "serial",
+    "sum",
+    "text",
+    "timestamp",
+    "varchar",
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn escape_keywords_for_each_language() {
+        assert_eq!(Language::Rust.escape("type"), "r#type");
+        assert_eq!(Language::Rust.escape("self"), "self_");
+        assert_eq!(Language::Rust.escape("Type"), "Type");
+        assert_eq!(Language::Python.escape("import"), "import_");
+        assert_eq!(Language::Python.escape("type"), "type_");
+        assert_eq!(Language::TypeScript.escape("class"), "class_");
+        assert_eq!(Language::Go.escape("func"), "func_");
+        assert_eq!(Language::Java.escape("class"), "class_");
+        assert_eq!(Language::CSharp.escape("class"), "@class");
+        assert_eq!(Language::Kotlin.escape("fun"), "`fun`");
+        assert_eq!(Language::Swift.escape("func"), "`func`");
+        assert_eq!(Language::Sql.escape("user"), "\"user\"");
+        assert_eq!(Language::Sql.escape("SELECT"), "\"SELECT\"");
+        assert_eq!(Language::Sql.escape("user_name"), "user_name");
+    }
+    #[test]
+    fn preserve_contextual_keywords_without_escape() {
+        assert!(Language::CSharp.is_preserved("var"));
+        assert_eq!(Language::CSharp.escape("var"), "var");
+        assert!(Language::TypeScript.is_preserved("type"));
+        assert!(Language::TypeScript.is_preserved("as"));
+        assert!(Language::TypeScript.is_preserved("async"));
+        assert!(Language::Go.is_preserved("int"));
+        assert!(Language::Rust.is_preserved("let"));
+        assert!(Language::Sql.is_preserved("COUNT"));
+        assert!(!Language::Go.is_preserved("user_id"));
+    }
+    #[test]
+    fn infer_language_from_extension() {
+        assert_eq!(Language::from_extension("rs"), Some(Language::Rust));
+        assert_eq!(Language::from_extension("py"), Some(Language::Python));
+        assert_eq!(Language::from_extension("tsx"), Some(Language::TypeScript));
+        assert_eq!(Language::from_extension("js"), Some(Language::TypeScript));
+        assert_eq!(Language::from_extension("KT"), Some(Language::Kotlin));
+        assert_eq!(Language::from_extension("txt"), None);
+    }
+    #[test]
+    fn every_language_has_name_and_keywords() {
+        for language in Language::all() {
+            assert_eq!(Language::from_name(language.name()), Some(*language));
+            assert!(!language.keywords().is_empty());
+        }
+    }
+}
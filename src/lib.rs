@@ -1,11 +1,29 @@
 pub mod convertor;
 pub mod corrector;
+pub mod dictionary;
+pub mod error;
+pub mod language;
+pub mod pre_convertor;
+pub mod rewrite;
+mod transliteration;
+mod trie;
+pub mod word;
+
+use std::convert::Infallible;
+
+use error::NpcError;
+use rewrite::{identifier_spans, RewriteOptions};
+use word::{
+    edge_separators, is_caseless, split_words_with, unmask, Caseless, SplitOptions, Word, WordKind,
+};
 
 pub trait PreConvert {
     fn convert(&self, source: &str, principal: Principal) -> String;
 }
+/// PostConvert receives the words split from the source and returns the words to render,
+/// so it can replace whole words or word sequences without touching the other words.
 pub trait PostConvert {
-    fn convert(&self, source: &str, principal: Principal) -> String;
+    fn convert(&self, words: Vec<Word>, principal: Principal) -> Vec<Word>;
 }
 
 impl<F> PreConvert for F
@@ -18,25 +36,60 @@ where
 }
 impl<F> PostConvert for F
 where
-    F: Fn(&str, Principal) -> String,
+    F: Fn(Vec<Word>, Principal) -> Vec<Word>,
 {
-    fn convert(&self, source: &str, principal: Principal) -> String {
-        self(source, principal)
+    fn convert(&self, words: Vec<Word>, principal: Principal) -> Vec<Word> {
+        self(words, principal)
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Principal {
     Camel,
     Pascal,
     Snake,
     Constant,
     Chain,
+    /// `Train-Case`
+    Train,
+    /// `dot.case`
+    Dot,
+    /// `path/case`
+    Path,
+    /// `Title Case`
+    Title,
+    /// `Sentence case`
+    Sentence,
+    /// `flatcase`
+    Flat,
+    /// `UPPERFLATCASE`
+    UpperFlat,
+    /// `COBOL-CASE`
+    Cobol,
+    /// `Ada_Case`
+    Ada,
+    /// `camel_Snake_Case`
+    CamelSnake,
+}
+
+impl Principal {
+    /// separator between words, None for the principal which joins words without separator
+    pub fn separator(&self) -> Option<char> {
+        match self {
+            Self::Snake | Self::Constant | Self::Ada | Self::CamelSnake => Some('_'),
+            Self::Chain | Self::Train | Self::Cobol => Some('-'),
+            Self::Dot => Some('.'),
+            Self::Path => Some('/'),
+            Self::Title | Self::Sentence => Some(' '),
+            Self::Camel | Self::Pascal | Self::Flat | Self::UpperFlat => None,
+        }
+    }
 }
 
 pub struct Parameter {
     source: String,
     to: Principal,
+    options: SplitOptions,
     pres: Vec<Box<dyn PreConvert>>,
     posts: Vec<Box<dyn PostConvert>>,
 }
@@ -45,6 +98,7 @@ impl Parameter {
         Self {
             source: source.into(),
             to,
+            options: SplitOptions::default(),
             posts: Vec::new(),
             pres: Vec::new(),
         }
@@ -61,26 +115,234 @@ impl Parameter {
         self.to = to;
         self
     }
+    pub fn change_source(mut self, source: impl Into<String>) -> Self {
+        self.source = source.into();
+        self
+    }
+    pub fn change_split_options(mut self, options: SplitOptions) -> Self {
+        self.options = options;
+        self
+    }
 }
 
 pub fn convert(param: &Parameter) -> String {
+    convert_source(param, &param.source)
+}
+
+fn convert_source(param: &Parameter, source: &str) -> String {
+    // the source can not mask words by itself, only the pre-converters do
     let result = param
         .pres
         .iter()
-        .fold(param.source.clone(), |acc, c| c.convert(&acc, param.to));
-
-    let result = match param.to {
-        Principal::Camel => to_camel(&result),
-        Principal::Pascal => to_pascal(&result),
-        Principal::Snake => to_snake(&result),
-        Principal::Constant => to_constant(&result),
-        Principal::Chain => to_chain(&result),
-    };
+        .fold(unmask(source), |acc, c| c.convert(&acc, param.to));
+
+    let convertor = NamingPrincipalConvertor::with_options(&result, param.options);
+    let words = param
+        .posts
+        .iter()
+        .fold(convertor.words().to_vec(), |acc, c| {
+            c.convert(acc, param.to)
+        });
+    convertor.change_words(words).to(param.to)
+}
+
+/// try_convert is the same as `convert` but returns an error
+/// when the source has nothing to convert or the result is not a valid identifier.
+///
+/// A valid identifier consists of words of alphanumeric characters or `_` joined by the separator of the principal,
+/// and verbatim words like the ones kept by `IgnoreWordsConverter` which are not checked.
+pub fn try_convert(param: &Parameter) -> Result<String, NpcError> {
+    try_convert_source(param, &param.source)
+}
 
-    param
+fn try_convert_source(param: &Parameter, source: &str) -> Result<String, NpcError> {
+    let result = param
+        .pres
+        .iter()
+        .fold(unmask(source), |acc, c| c.convert(&acc, param.to));
+
+    let convertor = NamingPrincipalConvertor::try_with_options(&result, param.options)?;
+    let words = param
         .posts
         .iter()
-        .fold(result, |acc, c| c.convert(&acc, param.to))
+        .fold(convertor.words().to_vec(), |acc, c| {
+            c.convert(acc, param.to)
+        });
+    // verbatim words, like ignored words, are kept as they are, and the other words are checked one by one
+    let is_valid = words.iter().all(|w| {
+        w.kind() == WordKind::Verbatim
+            || w.as_str().chars().all(|c| c.is_alphanumeric() || c == '_')
+    });
+    let result = convertor.change_words(words).to(param.to);
+    if !is_valid {
+        return Err(NpcError::InvalidIdentifier {
+            converted: result,
+            principal: param.to,
+        });
+    }
+    Ok(result)
+}
+
+/// rewrite_text converts every identifier in the text with the parameter, and keeps the rest of the text as it is.
+///
+/// The source of the parameter is not used, each identifier is the source instead.
+/// String literals and comments are kept unless `RewriteOptions` includes them.
+/// With a language, its keywords are kept and converted identifiers which are keywords are escaped.
+/// ## Example
+/// ```rust
+/// use npc::{rewrite::RewriteOptions, rewrite_text, Parameter, Principal};
+///
+/// let param = Parameter::new("", Principal::Snake);
+/// let text = "let userName = \"userName\"; // userName\n";
+/// assert_eq!(
+///     rewrite_text(text, &param, RewriteOptions::new()),
+///     "let user_name = \"userName\"; // userName\n"
+/// );
+/// assert_eq!(
+///     rewrite_text(text, &param, RewriteOptions::new().include_comments(true)),
+///     "let user_name = \"userName\"; // user_name\n"
+/// );
+/// ```
+pub fn rewrite_text(text: &str, param: &Parameter, options: RewriteOptions) -> String {
+    rewrite_with(text, &options, |source| {
+        Ok::<_, Infallible>(convert_source(param, source))
+    })
+    .unwrap_or_else(|never| match never {})
+}
+
+/// try_rewrite_text is the same as `rewrite_text` but returns the error of the first identifier which `try_convert` fails.
+pub fn try_rewrite_text(
+    text: &str,
+    param: &Parameter,
+    options: RewriteOptions,
+) -> Result<String, NpcError> {
+    rewrite_with(text, &options, |source| try_convert_source(param, source))
+}
+
+fn rewrite_with<E>(
+    text: &str,
+    options: &RewriteOptions,
+    convert: impl Fn(&str) -> Result<String, E>,
+) -> Result<String, E> {
+    let language = options.get_language();
+    let mut result = String::with_capacity(text.len());
+    let mut copied = 0;
+    for span in identifier_spans(text, options) {
+        result.push_str(&text[copied..span.start]);
+        let identifier = &text[span.clone()];
+        // a raw identifier like `r#type` is converted without `r#`, and escaped again if it is a keyword
+        let (raw, identifier) = match identifier.strip_prefix("r#") {
+            Some(identifier) => (true, identifier),
+            None => (false, identifier),
+        };
+        // `_` alone has no word, and keywords or predeclared names like `int` of Go are kept
+        if !identifier.chars().any(char::is_alphanumeric)
+            || (!raw && language.is_some_and(|l| l.is_preserved(identifier)))
+        {
+            result.push_str(&text[span.clone()]);
+        } else {
+            // leading and trailing `_` are kept as they are, like `__init__` or `_private`
+            let word = identifier.trim_matches('_');
+            let head = identifier.len() - identifier.trim_start_matches('_').len();
+            let converted = format!(
+                "{}{}{}",
+                &identifier[..head],
+                convert(word)?,
+                &identifier[head + word.len()..]
+            );
+            match language {
+                Some(language) => result.push_str(&language.escape(&converted)),
+                None => result.push_str(&converted),
+            }
+        }
+        copied = span.end;
+    }
+    result.push_str(&text[copied..]);
+    Ok(result)
+}
+
+/// Confidence of the best guess of `Detection`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Confidence {
+    /// only one principal matches
+    Certain,
+    /// several principals match, like `"flatcase"` is snake, camel and chain at once
+    Ambiguous,
+    /// no principal matches
+    Unknown,
+}
+
+/// Detection is the result of `detect`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Detection {
+    candidates: Vec<Principal>,
+    best: Option<Principal>,
+}
+
+impl Detection {
+    /// every principal the source could be
+    pub fn candidates(&self) -> &[Principal] {
+        &self.candidates
+    }
+    /// the most specific principal of candidates, like `Flat` for `"flatcase"`
+    pub fn best(&self) -> Option<Principal> {
+        self.best
+    }
+    pub fn is_ambiguous(&self) -> bool {
+        self.candidates.len() > 1
+    }
+    pub fn confidence(&self) -> Confidence {
+        match self.candidates.len() {
+            0 => Confidence::Unknown,
+            1 => Confidence::Certain,
+            _ => Confidence::Ambiguous,
+        }
+    }
+}
+
+type Detector = fn(&str) -> bool;
+
+/// detect returns every principal the source could be with the best guess.
+///
+/// ## Example
+/// ```rust
+/// use npc::{detect, Confidence, Principal};
+///
+/// let detection = detect("flatcase");
+/// assert_eq!(detection.best(), Some(Principal::Flat));
+/// assert!(detection.candidates().contains(&Principal::Snake));
+/// assert_eq!(detection.confidence(), Confidence::Ambiguous);
+///
+/// let detection = detect("snake_case");
+/// assert_eq!(detection.candidates(), &[Principal::Snake]);
+/// assert_eq!(detection.confidence(), Confidence::Certain);
+/// ```
+pub fn detect(source: &str) -> Detection {
+    let detectors: [(Principal, Detector); 15] = [
+        (Principal::Camel, is_camel),
+        (Principal::Pascal, is_pascal),
+        (Principal::Snake, is_snake),
+        (Principal::Constant, is_constant),
+        (Principal::Chain, is_chain),
+        (Principal::Train, is_train),
+        (Principal::Dot, is_dot),
+        (Principal::Path, is_path),
+        (Principal::Title, is_title),
+        (Principal::Sentence, is_sentence),
+        (Principal::Flat, is_flat),
+        (Principal::UpperFlat, is_upper_flat),
+        (Principal::Cobol, is_cobol),
+        (Principal::Ada, is_ada),
+        (Principal::CamelSnake, is_camel_snake),
+    ];
+    Detection {
+        candidates: detectors
+            .iter()
+            .filter(|(_, is)| is(source))
+            .map(|(principal, _)| *principal)
+            .collect(),
+        best: NamingPrincipal::new(source).principal(),
+    }
 }
 
 pub fn to_camel(source: &str) -> String {
@@ -98,6 +360,36 @@ pub fn to_constant(source: &str) -> String {
 pub fn to_chain(source: &str) -> String {
     NamingPrincipalConvertor::new(source).to_chain()
 }
+pub fn to_train(source: &str) -> String {
+    NamingPrincipalConvertor::new(source).to_train()
+}
+pub fn to_dot(source: &str) -> String {
+    NamingPrincipalConvertor::new(source).to_dot()
+}
+pub fn to_path(source: &str) -> String {
+    NamingPrincipalConvertor::new(source).to_path()
+}
+pub fn to_title(source: &str) -> String {
+    NamingPrincipalConvertor::new(source).to_title()
+}
+pub fn to_sentence(source: &str) -> String {
+    NamingPrincipalConvertor::new(source).to_sentence()
+}
+pub fn to_flat(source: &str) -> String {
+    NamingPrincipalConvertor::new(source).to_flat()
+}
+pub fn to_upper_flat(source: &str) -> String {
+    NamingPrincipalConvertor::new(source).to_upper_flat()
+}
+pub fn to_cobol(source: &str) -> String {
+    NamingPrincipalConvertor::new(source).to_cobol()
+}
+pub fn to_ada(source: &str) -> String {
+    NamingPrincipalConvertor::new(source).to_ada()
+}
+pub fn to_camel_snake(source: &str) -> String {
+    NamingPrincipalConvertor::new(source).to_camel_snake()
+}
 
 /// This module provides a struct `NamingPrincipalConvertor` that converts
 /// a string to various naming conventions such as camel case, snake case,
@@ -115,329 +407,280 @@ pub fn to_chain(source: &str) -> String {
 /// assert_eq!(convertor.to_snake(), "some_snake_case_name");
 /// assert_eq!(convertor.to_chain(), "some-snake-case-name");
 /// assert_eq!(convertor.to_constant(), "SOME_SNAKE_CASE_NAME");
+/// assert_eq!(convertor.to_train(), "Some-Snake-Case-Name");
+/// assert_eq!(convertor.to_dot(), "some.snake.case.name");
+/// assert_eq!(convertor.to_path(), "some/snake/case/name");
+/// assert_eq!(convertor.to_title(), "Some Snake Case Name");
+/// assert_eq!(convertor.to_sentence(), "Some snake case name");
 /// ```
 #[derive(Debug, Clone)]
 pub struct NamingPrincipalConvertor<'a> {
-    original: &'a str,
     principal: NamingPrincipal<'a>,
+    words: Vec<Word>,
 }
 
 impl<'a> NamingPrincipalConvertor<'a> {
     pub fn new(source: &'a str) -> Self {
+        Self::with_options(source, SplitOptions::default())
+    }
+    pub fn with_options(source: &'a str, options: SplitOptions) -> Self {
         Self {
-            original: source,
             principal: NamingPrincipal::new(source),
+            words: split_words_with(source, &options),
+        }
+    }
+    /// try_new returns an error if source is empty or has no word to convert
+    pub fn try_new(source: &'a str) -> Result<Self, NpcError> {
+        Self::try_with_options(source, SplitOptions::default())
+    }
+    pub fn try_with_options(source: &'a str, options: SplitOptions) -> Result<Self, NpcError> {
+        if options.get_caseless() == Caseless::Reject && source.chars().any(is_caseless) {
+            return Err(NpcError::CaselessInput(source.to_string()));
         }
+        let this = Self {
+            principal: NamingPrincipal::try_new(source)?,
+            words: split_words_with(source, &options),
+        };
+        let has_word = this
+            .words
+            .iter()
+            .any(|w| w.as_str().chars().any(|c| c.is_alphanumeric()));
+        if !has_word {
+            return Err(NpcError::UnsupportedInput(source.to_string()));
+        }
+        Ok(this)
     }
     pub fn is_non_principal(&self) -> bool {
-        is_non_principal(self.original)
+        is_non_principal(self.original())
     }
     pub fn is_constant(&self) -> bool {
-        is_constant(self.original)
+        is_constant(self.original())
     }
     pub fn is_chain(&self) -> bool {
-        is_chain(self.original)
+        is_chain(self.original())
     }
     pub fn is_pascal(&self) -> bool {
-        is_pascal(self.original)
+        is_pascal(self.original())
     }
     pub fn is_snake(&self) -> bool {
-        is_snake(self.original)
+        is_snake(self.original())
     }
     pub fn is_camel(&self) -> bool {
-        is_camel(self.original)
+        is_camel(self.original())
     }
-    pub fn original(&self) -> &str {
-        self.original
+    pub fn is_flat(&self) -> bool {
+        is_flat(self.original())
     }
-    pub fn to_chain(&self) -> String {
-        match self.principal {
-            NamingPrincipal::Snake(snake) => snake.replace("_", "-"),
-            NamingPrincipal::Camel(camel) => camel.chars().fold(String::new(), |mut acc, c| {
-                Self::upper_to_partition(&mut acc, c, '-');
-                acc
-            }),
-            NamingPrincipal::Constant(constant) => constant
-                .chars()
-                .map(|c| {
-                    if c == '_' {
-                        '-'
-                    } else {
-                        c.to_ascii_lowercase()
-                    }
-                })
-                .collect(),
-            NamingPrincipal::Pascal(pascal) => {
-                pascal
-                    .chars()
-                    .enumerate()
-                    .fold(String::new(), |mut acc, (i, cur)| {
-                        if i == 0 {
-                            acc.push(cur.to_ascii_lowercase());
-                            return acc;
-                        }
-                        Self::upper_to_partition(&mut acc, cur, '-');
-                        acc
-                    })
-            }
-            NamingPrincipal::NonPrincipal(_) => {
-                let snake = self.to_snake();
-                let np = NamingPrincipalConvertor::new(&snake);
-                np.to_chain()
-            }
-            _ => self.original.to_string(),
+    pub fn is_upper_flat(&self) -> bool {
+        is_upper_flat(self.original())
+    }
+    pub fn is_cobol(&self) -> bool {
+        is_cobol(self.original())
+    }
+    pub fn is_ada(&self) -> bool {
+        is_ada(self.original())
+    }
+    pub fn is_camel_snake(&self) -> bool {
+        is_camel_snake(self.original())
+    }
+    pub fn is_train(&self) -> bool {
+        is_train(self.original())
+    }
+    pub fn is_dot(&self) -> bool {
+        is_dot(self.original())
+    }
+    pub fn is_path(&self) -> bool {
+        is_path(self.original())
+    }
+    pub fn is_title(&self) -> bool {
+        is_title(self.original())
+    }
+    pub fn is_sentence(&self) -> bool {
+        is_sentence(self.original())
+    }
+    pub fn original(&self) -> &'a str {
+        self.principal.source()
+    }
+    pub fn words(&self) -> &[Word] {
+        &self.words
+    }
+    /// change_words replaces the words to render, the original source is kept
+    pub fn change_words(mut self, words: Vec<Word>) -> Self {
+        self.words = words;
+        self
+    }
+    pub fn to(&self, principal: Principal) -> String {
+        match principal {
+            Principal::Camel => self.to_camel(),
+            Principal::Pascal => self.to_pascal(),
+            Principal::Snake => self.to_snake(),
+            Principal::Constant => self.to_constant(),
+            Principal::Chain => self.to_chain(),
+            Principal::Train => self.to_train(),
+            Principal::Dot => self.to_dot(),
+            Principal::Path => self.to_path(),
+            Principal::Title => self.to_title(),
+            Principal::Sentence => self.to_sentence(),
+            Principal::Flat => self.to_flat(),
+            Principal::UpperFlat => self.to_upper_flat(),
+            Principal::Cobol => self.to_cobol(),
+            Principal::Ada => self.to_ada(),
+            Principal::CamelSnake => self.to_camel_snake(),
         }
     }
+    pub fn to_chain(&self) -> String {
+        self.join_separated('-', |_, w| Self::lower(w))
+    }
     pub fn to_constant(&self) -> String {
-        match self.principal {
-            NamingPrincipal::Snake(snake) => {
-                snake.chars().map(|c| c.to_ascii_uppercase()).collect()
-            }
-            NamingPrincipal::Chain(chain) => chain
-                .chars()
-                .map(|c| {
-                    if c == '-' {
-                        '_'
-                    } else {
-                        c.to_ascii_uppercase()
-                    }
-                })
-                .collect(),
-            NamingPrincipal::Flat(flat) => flat.chars().map(|c| c.to_ascii_uppercase()).collect(),
-            NamingPrincipal::Camel(_) => {
-                let snake = self.to_snake();
-                snake.chars().map(|c| c.to_ascii_uppercase()).collect()
-            }
-            NamingPrincipal::Pascal(_) => {
-                let snake = self.to_snake();
-                snake.chars().map(|c| c.to_ascii_uppercase()).collect()
-            }
-            NamingPrincipal::NonPrincipal(_) => {
-                let snake = self.to_snake();
-                snake.chars().map(|c| c.to_ascii_uppercase()).collect()
-            }
-            _ => self.original.to_string(),
-        }
+        self.join_separated('_', |_, w| Self::upper(w))
     }
     pub fn to_pascal(&self) -> String {
-        match self.principal {
-            NamingPrincipal::Snake(snake) => Self::split_case_to_pascal(snake, '_'),
-            NamingPrincipal::Chain(chain) => Self::split_case_to_pascal(chain, '-'),
-            NamingPrincipal::Flat(flat) => Self::first_char_to_upper(flat),
-            NamingPrincipal::Camel(camel) => Self::first_char_to_upper(camel),
-            NamingPrincipal::NonPrincipal(_) => {
-                let snake = self.to_snake();
-                Self::split_case_to_pascal(&snake, '_')
-            }
-            NamingPrincipal::Constant(constant) => {
-                let mut result = String::new();
-                let mut next_is_upper_flag = true;
-                for (i, c) in constant.chars().enumerate() {
-                    if c == '_' {
-                        if i != 0 {
-                            next_is_upper_flag = true;
-                        }
-                        continue;
-                    }
-                    if next_is_upper_flag {
-                        result.push(c);
-                        next_is_upper_flag = false;
-                        continue;
-                    }
-                    result.push(c.to_ascii_lowercase())
-                }
-                result
-            }
-            _ => self.original.to_string(),
-        }
+        self.join(|_, w| Self::capitalize_word(w))
     }
     pub fn to_camel(&self) -> String {
-        match self.principal {
-            NamingPrincipal::Chain(chain) => Self::split_case_to_camel(chain, '-'),
-            NamingPrincipal::Snake(snake) => Self::split_case_to_camel(snake, '_'),
-            NamingPrincipal::Constant(constant) => {
-                let mut result = String::new();
-                let mut next_is_upper_flag = false;
-                for (i, c) in constant.chars().enumerate() {
-                    if next_is_upper_flag {
-                        result.push(c);
-                        next_is_upper_flag = false;
-                        continue;
-                    }
-                    if c == '_' {
-                        if i != 0 {
-                            next_is_upper_flag = true;
-                        }
-                        continue;
-                    }
-                    result.push(c.to_ascii_lowercase())
-                }
-                result
+        self.join(|i, w| {
+            if i == 0 {
+                Self::lower(w)
+            } else {
+                Self::capitalize_word(w)
             }
-            NamingPrincipal::Pascal(pascal) => pascal
-                .chars()
-                .enumerate()
-                .map(|(i, c)| if i == 0 { c.to_ascii_lowercase() } else { c })
-                .collect::<String>(),
-            NamingPrincipal::NonPrincipal(_) => {
-                let snake = self.to_snake();
-                NamingPrincipalConvertor::new(&snake).to_camel()
-            }
-            _ => self.original.to_string(),
-        }
+        })
     }
     pub fn to_snake(&self) -> String {
-        match self.principal {
-            NamingPrincipal::Camel(camel) => {
-                let mut is_upper_sequence = false;
-                let mut result = String::new();
-                for c in camel.chars() {
-                    if is_upper_sequence && c.is_uppercase() {
-                        result.push(c.to_ascii_lowercase());
-                        continue;
-                    }
-                    if !is_upper_sequence && c.is_uppercase() {
-                        is_upper_sequence = true;
-                        Self::upper_to_partition(&mut result, c, '_');
-                        continue;
-                    }
-                    result.push(c);
-                    is_upper_sequence = false;
-                }
-                result
-            }
-            NamingPrincipal::Pascal(pascal) => {
-                pascal
-                    .chars()
-                    .enumerate()
-                    .fold(String::new(), |mut acc, (i, cur)| {
-                        if i == 0 {
-                            acc.push(cur.to_ascii_lowercase());
-                            return acc;
-                        }
-                        Self::upper_to_partition(&mut acc, cur, '_');
-                        acc
-                    })
+        self.join_separated('_', |_, w| Self::lower(w))
+    }
+    pub fn to_train(&self) -> String {
+        self.join_separated('-', |_, w| Self::capitalize_word(w))
+    }
+    pub fn to_dot(&self) -> String {
+        self.join_separated('.', |_, w| Self::lower(w))
+    }
+    pub fn to_path(&self) -> String {
+        self.join_separated('/', |_, w| Self::lower(w))
+    }
+    pub fn to_title(&self) -> String {
+        self.join_separated(' ', |_, w| Self::capitalize_word(w))
+    }
+    pub fn to_sentence(&self) -> String {
+        self.join_separated(' ', |i, w| {
+            if i == 0 {
+                Self::capitalize_word(w)
+            } else {
+                Self::lower_in_mixed_case(w)
             }
-            NamingPrincipal::Constant(constant) => constant
-                .chars()
-                .map(|c| c.to_ascii_lowercase())
-                .collect::<String>(),
-            NamingPrincipal::Chain(chain) => chain.replace("-", "_"),
-            NamingPrincipal::NonPrincipal(non_principal) => {
-                non_principal
-                    .chars()
-                    .enumerate()
-                    .fold(String::new(), |mut acc, (i, cur)| {
-                        if i == 0 && cur.is_uppercase() {
-                            acc.push(cur.to_ascii_lowercase());
-                            return acc;
-                        }
-                        if cur == '-' {
-                            // case - before upper
-                            // ex: A-D
-                            // if not impl below process
-                            // to be a__d
-                            if let Some(Some(next_char)) = non_principal
-                                .get(i + 1..i + 2)
-                                .map(|str| str.chars().next())
-                            {
-                                if next_char.is_uppercase() {
-                                    return acc;
-                                }
-                            }
-                            acc.push('_');
-                            return acc;
-                        }
-                        Self::upper_to_partition(&mut acc, cur, '_');
-                        acc
-                    })
+        })
+    }
+    pub fn to_flat(&self) -> String {
+        self.join(|_, w| Self::lower(w))
+    }
+    pub fn to_upper_flat(&self) -> String {
+        self.join(|_, w| Self::upper(w))
+    }
+    pub fn to_cobol(&self) -> String {
+        self.join_separated('-', |_, w| Self::upper(w))
+    }
+    pub fn to_ada(&self) -> String {
+        self.join_separated('_', |_, w| Self::capitalize_word(w))
+    }
+    pub fn to_camel_snake(&self) -> String {
+        self.join_separated('_', |i, w| {
+            if i == 0 {
+                Self::lower(w)
+            } else {
+                Self::capitalize_word(w)
             }
-            _ => self.original.to_string(),
+        })
+    }
+    fn join(&self, case: impl Fn(usize, &Word) -> String) -> String {
+        self.words
+            .iter()
+            .enumerate()
+            .map(|(i, w)| case(i, w))
+            .collect()
+    }
+    // separators at the head and the tail of the original are kept as the given separator
+    fn join_separated(&self, separator: char, case: impl Fn(usize, &Word) -> String) -> String {
+        let (leading, trailing) = edge_separators(self.original());
+        let body = self
+            .words
+            .iter()
+            .enumerate()
+            .map(|(i, w)| case(i, w))
+            .collect::<Vec<_>>()
+            .join(separator.to_string().as_str());
+        let mut result = separator.to_string().repeat(leading);
+        result.push_str(&body);
+        result.push_str(&separator.to_string().repeat(trailing));
+        result
+    }
+    fn lower(word: &Word) -> String {
+        match word.kind() {
+            WordKind::Verbatim => word.as_str().to_string(),
+            _ => word.as_str().to_lowercase(),
         }
     }
-    fn upper_to_partition(acc: &mut String, c: char, partition: char) {
-        if c.is_uppercase() {
-            acc.push(partition);
-            acc.push(c.to_ascii_lowercase());
-        } else {
-            acc.push(c);
+    fn upper(word: &Word) -> String {
+        match word.kind() {
+            WordKind::Verbatim => word.as_str().to_string(),
+            _ => word.as_str().to_uppercase(),
         }
     }
-    fn first_char_to_upper(source: &str) -> String {
-        source
-            .chars()
-            .enumerate()
-            .map(|(i, c)| if i == 0 { c.to_ascii_uppercase() } else { c })
-            .collect()
+    // lower case except reserved words which keep their own case, like `GitHub` in `Use GitHub`
+    fn lower_in_mixed_case(word: &Word) -> String {
+        match word.kind() {
+            WordKind::Normal => word.as_str().to_lowercase(),
+            _ => word.as_str().to_string(),
+        }
     }
-    fn split_case_to_camel(source: &str, split: char) -> String {
-        let mut result = String::new();
-        let mut next_is_upper_flag = false;
-        for (i, c) in source.chars().enumerate() {
-            if next_is_upper_flag {
-                result.push(c.to_ascii_uppercase());
-                next_is_upper_flag = false;
-                continue;
-            }
-            if c == split && i != 0 {
-                next_is_upper_flag = true;
-                continue;
-            }
-            if c == split && i == 0 {
-                continue;
-            }
-            result.push(c);
+    fn capitalize_word(word: &Word) -> String {
+        match word.kind() {
+            WordKind::Normal => Self::capitalize(word.as_str()),
+            _ => word.as_str().to_string(),
         }
-        result
     }
-    fn split_case_to_pascal(source: &str, split: char) -> String {
-        let mut result = String::new();
-        let mut next_is_upper_flag = false;
-        for (i, c) in source.chars().enumerate() {
-            if next_is_upper_flag {
-                result.push(c.to_ascii_uppercase());
-                next_is_upper_flag = false;
-                continue;
-            }
-            if c == split {
-                next_is_upper_flag = true;
-                continue;
-            }
-            if c != split && i == 0 {
-                result.push(c.to_ascii_uppercase());
-                continue;
+    fn capitalize(word: &str) -> String {
+        let mut chars = word.chars();
+        match chars.next() {
+            Some(first) => {
+                let mut result = first.to_uppercase().to_string();
+                result.push_str(&chars.as_str().to_lowercase());
+                result
             }
-            result.push(c);
+            None => String::new(),
         }
-        result
     }
 }
 
 #[cfg(test)]
-pub(self) mod naming_principal_test_data {
-    pub(crate) const FLATCASE: &'static str = "flatcase";
-    pub(crate) const EMPTYCASE: &'static str = "";
-    pub(crate) const SNAKE_CASE1: &'static str = "snake_case";
-    pub(crate) const SNAKE_CASE2: &'static str = "_snake_case";
-    pub(crate) const CAMEL_CASE: &'static str = "camelCase";
-    pub(crate) const CAMEL_CASE2: &'static str = "internetIP";
-    pub(crate) const PASCAL_CASE1: &'static str = "PascalCase";
-    pub(crate) const PASCAL_CASE2: &'static str = "ABCData";
-    pub(crate) const CONSTANT_CASE1: &'static str = "CONSTANT_CASE";
-    pub(crate) const CONSTANT_CASE2: &'static str = "CONSTANT";
-    pub(crate) const CONSTANT_CASE3: &'static str = "_CONSTANT_CASE";
-    pub(crate) const CHAIN_CASE1: &'static str = "chain-case";
-    pub(crate) const CHAIN_CASE2: &'static str = "-chain-case";
-    pub(crate) const NONPRINCIPAL_CASE1: &'static str = "A_data";
-    pub(crate) const NONPRINCIPAL_CASE2: &'static str = "ABC-Data_";
-    pub(crate) const NONPRINCIPAL_CASE3: &'static str = "ABC- Data";
+mod naming_principal_test_data {
+    pub(crate) const FLATCASE: &str = "flatcase";
+    pub(crate) const EMPTYCASE: &str = "";
+    pub(crate) const SNAKE_CASE1: &str = "snake_case";
+    pub(crate) const SNAKE_CASE2: &str = "_snake_case";
+    pub(crate) const CAMEL_CASE: &str = "camelCase";
+    pub(crate) const CAMEL_CASE2: &str = "internetIP";
+    pub(crate) const PASCAL_CASE1: &str = "PascalCase";
+    pub(crate) const PASCAL_CASE2: &str = "ABCData";
+    pub(crate) const CONSTANT_CASE1: &str = "CONSTANT_CASE";
+    pub(crate) const CONSTANT_CASE2: &str = "CONSTANT";
+    pub(crate) const CONSTANT_CASE3: &str = "_CONSTANT_CASE";
+    pub(crate) const CHAIN_CASE1: &str = "chain-case";
+    pub(crate) const CHAIN_CASE2: &str = "-chain-case";
+    pub(crate) const UPPER_FLATCASE: &str = "UPPERFLATCASE";
+    pub(crate) const COBOL_CASE: &str = "COBOL-CASE";
+    pub(crate) const ADA_CASE: &str = "Ada_Case";
+    pub(crate) const CAMEL_SNAKE_CASE: &str = "camel_Snake_Case";
+    pub(crate) const NONPRINCIPAL_CASE1: &str = "A_data";
+    pub(crate) const NONPRINCIPAL_CASE2: &str = "ABC-Data_";
+    pub(crate) const NONPRINCIPAL_CASE3: &str = "ABC- Data";
 }
 
 #[cfg(test)]
 mod test_convertor {
     use super::*;
+    use convertor::IgnoreWordsConverter;
     use naming_principal_test_data::*;
+    use word::{Acronym, Digit};
     #[test]
     fn test_to_chain() {
         let convertor = NamingPrincipalConvertor::new(FLATCASE);
@@ -450,6 +693,8 @@ mod test_convertor {
         assert_eq!(convertor.to_chain(), "-snake-case".to_string());
         let convertor = NamingPrincipalConvertor::new(CAMEL_CASE);
         assert_eq!(convertor.to_chain(), "camel-case".to_string());
+        let convertor = NamingPrincipalConvertor::new(CAMEL_CASE2);
+        assert_eq!(convertor.to_chain(), "internet-ip".to_string());
         let convertor = NamingPrincipalConvertor::new(CONSTANT_CASE1);
         assert_eq!(convertor.to_chain(), "constant-case".to_string());
         let convertor = NamingPrincipalConvertor::new(CONSTANT_CASE2);
@@ -459,7 +704,7 @@ mod test_convertor {
         let convertor = NamingPrincipalConvertor::new(PASCAL_CASE1);
         assert_eq!(convertor.to_chain(), "pascal-case".to_string());
         let convertor = NamingPrincipalConvertor::new(PASCAL_CASE2);
-        assert_eq!(convertor.to_chain(), "a-b-c-data".to_string());
+        assert_eq!(convertor.to_chain(), "abc-data".to_string());
         let convertor = NamingPrincipalConvertor::new(CHAIN_CASE1);
         assert_eq!(convertor.to_chain(), "chain-case".to_string());
         let convertor = NamingPrincipalConvertor::new(CHAIN_CASE2);
@@ -467,7 +712,7 @@ mod test_convertor {
         let convertor = NamingPrincipalConvertor::new(NONPRINCIPAL_CASE1);
         assert_eq!(convertor.to_chain(), "a-data".to_string());
         let convertor = NamingPrincipalConvertor::new(NONPRINCIPAL_CASE2);
-        assert_eq!(convertor.to_chain(), "a-b-c-data-".to_string());
+        assert_eq!(convertor.to_chain(), "abc-data-".to_string());
     }
     #[test]
     fn test_to_constant() {
@@ -481,6 +726,8 @@ mod test_convertor {
         assert_eq!(convertor.to_constant(), "_SNAKE_CASE".to_string());
         let convertor = NamingPrincipalConvertor::new(CAMEL_CASE);
         assert_eq!(convertor.to_constant(), "CAMEL_CASE".to_string());
+        let convertor = NamingPrincipalConvertor::new(CAMEL_CASE2);
+        assert_eq!(convertor.to_constant(), "INTERNET_IP".to_string());
         let convertor = NamingPrincipalConvertor::new(CONSTANT_CASE1);
         assert_eq!(convertor.to_constant(), "CONSTANT_CASE".to_string());
         let convertor = NamingPrincipalConvertor::new(CONSTANT_CASE2);
@@ -490,7 +737,7 @@ mod test_convertor {
         let convertor = NamingPrincipalConvertor::new(PASCAL_CASE1);
         assert_eq!(convertor.to_constant(), "PASCAL_CASE".to_string());
         let convertor = NamingPrincipalConvertor::new(PASCAL_CASE2);
-        assert_eq!(convertor.to_constant(), "A_B_C_DATA".to_string());
+        assert_eq!(convertor.to_constant(), "ABC_DATA".to_string());
         let convertor = NamingPrincipalConvertor::new(CHAIN_CASE1);
         assert_eq!(convertor.to_constant(), "CHAIN_CASE".to_string());
         let convertor = NamingPrincipalConvertor::new(CHAIN_CASE2);
@@ -498,7 +745,7 @@ mod test_convertor {
         let convertor = NamingPrincipalConvertor::new(NONPRINCIPAL_CASE1);
         assert_eq!(convertor.to_constant(), "A_DATA".to_string());
         let convertor = NamingPrincipalConvertor::new(NONPRINCIPAL_CASE2);
-        assert_eq!(convertor.to_constant(), "A_B_C_DATA_".to_string());
+        assert_eq!(convertor.to_constant(), "ABC_DATA_".to_string());
     }
 
     #[test]
@@ -513,6 +760,8 @@ mod test_convertor {
         assert_eq!(convertor.to_pascal(), "SnakeCase".to_string());
         let convertor = NamingPrincipalConvertor::new(CAMEL_CASE);
         assert_eq!(convertor.to_pascal(), "CamelCase".to_string());
+        let convertor = NamingPrincipalConvertor::new(CAMEL_CASE2);
+        assert_eq!(convertor.to_pascal(), "InternetIp".to_string());
         let convertor = NamingPrincipalConvertor::new(CONSTANT_CASE1);
         assert_eq!(convertor.to_pascal(), "ConstantCase".to_string());
         let convertor = NamingPrincipalConvertor::new(CONSTANT_CASE2);
@@ -522,7 +771,7 @@ mod test_convertor {
         let convertor = NamingPrincipalConvertor::new(PASCAL_CASE1);
         assert_eq!(convertor.to_pascal(), "PascalCase".to_string());
         let convertor = NamingPrincipalConvertor::new(PASCAL_CASE2);
-        assert_eq!(convertor.to_pascal(), "ABCData".to_string());
+        assert_eq!(convertor.to_pascal(), "AbcData".to_string());
         let convertor = NamingPrincipalConvertor::new(CHAIN_CASE1);
         assert_eq!(convertor.to_pascal(), "ChainCase".to_string());
         let convertor = NamingPrincipalConvertor::new(CHAIN_CASE2);
@@ -530,7 +779,7 @@ mod test_convertor {
         let convertor = NamingPrincipalConvertor::new(NONPRINCIPAL_CASE1);
         assert_eq!(convertor.to_pascal(), "AData".to_string());
         let convertor = NamingPrincipalConvertor::new(NONPRINCIPAL_CASE2);
-        assert_eq!(convertor.to_pascal(), "ABCData".to_string());
+        assert_eq!(convertor.to_pascal(), "AbcData".to_string());
     }
     #[test]
     fn test_to_camel() {
@@ -544,6 +793,8 @@ mod test_convertor {
         assert_eq!(convertor.to_camel(), "snakeCase".to_string());
         let convertor = NamingPrincipalConvertor::new(CAMEL_CASE);
         assert_eq!(convertor.to_camel(), CAMEL_CASE.to_string());
+        let convertor = NamingPrincipalConvertor::new(CAMEL_CASE2);
+        assert_eq!(convertor.to_camel(), "internetIp".to_string());
         let convertor = NamingPrincipalConvertor::new(CONSTANT_CASE1);
         assert_eq!(convertor.to_camel(), "constantCase".to_string());
         let convertor = NamingPrincipalConvertor::new(CONSTANT_CASE2);
@@ -553,7 +804,7 @@ mod test_convertor {
         let convertor = NamingPrincipalConvertor::new(PASCAL_CASE1);
         assert_eq!(convertor.to_camel(), "pascalCase".to_string());
         let convertor = NamingPrincipalConvertor::new(PASCAL_CASE2);
-        assert_eq!(convertor.to_camel(), "aBCData".to_string());
+        assert_eq!(convertor.to_camel(), "abcData".to_string());
         let convertor = NamingPrincipalConvertor::new(CHAIN_CASE1);
         assert_eq!(convertor.to_camel(), "chainCase".to_string());
         let convertor = NamingPrincipalConvertor::new(CHAIN_CASE2);
@@ -561,7 +812,7 @@ mod test_convertor {
         let convertor = NamingPrincipalConvertor::new(NONPRINCIPAL_CASE1);
         assert_eq!(convertor.to_camel(), "aData".to_string());
         let convertor = NamingPrincipalConvertor::new(NONPRINCIPAL_CASE2);
-        assert_eq!(convertor.to_camel(), "aBCData".to_string());
+        assert_eq!(convertor.to_camel(), "abcData".to_string());
     }
     #[test]
     fn test_to_snake() {
@@ -586,7 +837,7 @@ mod test_convertor {
         let convertor = NamingPrincipalConvertor::new(PASCAL_CASE1);
         assert_eq!(convertor.to_snake(), "pascal_case".to_string());
         let convertor = NamingPrincipalConvertor::new(PASCAL_CASE2);
-        assert_eq!(convertor.to_snake(), "a_b_c_data".to_string());
+        assert_eq!(convertor.to_snake(), "abc_data".to_string());
         let convertor = NamingPrincipalConvertor::new(CHAIN_CASE1);
         assert_eq!(convertor.to_snake(), "chain_case".to_string());
         let convertor = NamingPrincipalConvertor::new(CHAIN_CASE2);
@@ -594,7 +845,319 @@ mod test_convertor {
         let convertor = NamingPrincipalConvertor::new(NONPRINCIPAL_CASE1);
         assert_eq!(convertor.to_snake(), "a_data".to_string());
         let convertor = NamingPrincipalConvertor::new(NONPRINCIPAL_CASE2);
-        assert_eq!(convertor.to_snake(), "a_b_c_data_".to_string());
+        assert_eq!(convertor.to_snake(), "abc_data_".to_string());
+    }
+    #[test]
+    fn test_to_separated_by_other_characters() {
+        let convertor = NamingPrincipalConvertor::new(PASCAL_CASE1);
+        assert_eq!(convertor.to_train(), "Pascal-Case".to_string());
+        assert_eq!(convertor.to_dot(), "pascal.case".to_string());
+        assert_eq!(convertor.to_path(), "pascal/case".to_string());
+        assert_eq!(convertor.to_title(), "Pascal Case".to_string());
+        assert_eq!(convertor.to_sentence(), "Pascal case".to_string());
+        let convertor = NamingPrincipalConvertor::new(SNAKE_CASE2);
+        assert_eq!(convertor.to_train(), "-Snake-Case".to_string());
+        assert_eq!(convertor.to_dot(), ".snake.case".to_string());
+        let convertor = NamingPrincipalConvertor::new(PASCAL_CASE2);
+        assert_eq!(convertor.to_train(), "Abc-Data".to_string());
+        assert_eq!(convertor.to_sentence(), "Abc data".to_string());
+        let convertor = NamingPrincipalConvertor::new(EMPTYCASE);
+        assert_eq!(convertor.to_title(), "".to_string());
+        // all of them are split into the same words
+        for source in [
+            "Http-Header-Case",
+            "http.header.case",
+            "http/header/case",
+            "Http Header Case",
+            "Http header case",
+        ] {
+            assert_eq!(to_snake(source), "http_header_case".to_string());
+        }
+    }
+    #[test]
+    fn test_to_flat_and_legacy_cases() {
+        let convertor = NamingPrincipalConvertor::new(PASCAL_CASE1);
+        assert_eq!(convertor.to_flat(), "pascalcase".to_string());
+        assert_eq!(convertor.to_upper_flat(), "PASCALCASE".to_string());
+        assert_eq!(convertor.to_cobol(), "PASCAL-CASE".to_string());
+        assert_eq!(convertor.to_ada(), "Pascal_Case".to_string());
+        assert_eq!(convertor.to_camel_snake(), "pascal_Case".to_string());
+        let convertor = NamingPrincipalConvertor::new(SNAKE_CASE2);
+        assert_eq!(convertor.to_flat(), "snakecase".to_string());
+        assert_eq!(convertor.to_cobol(), "-SNAKE-CASE".to_string());
+        assert_eq!(convertor.to_ada(), "_Snake_Case".to_string());
+        let convertor = NamingPrincipalConvertor::new(COBOL_CASE);
+        assert_eq!(convertor.to_snake(), "cobol_case".to_string());
+        let convertor = NamingPrincipalConvertor::new(ADA_CASE);
+        assert_eq!(convertor.to_camel(), "adaCase".to_string());
+        let convertor = NamingPrincipalConvertor::new(CAMEL_SNAKE_CASE);
+        assert_eq!(convertor.to_constant(), "CAMEL_SNAKE_CASE".to_string());
+        let convertor = NamingPrincipalConvertor::new(EMPTYCASE);
+        assert_eq!(convertor.to_upper_flat(), "".to_string());
+    }
+    #[test]
+    fn test_try_new() {
+        let convertor = NamingPrincipalConvertor::try_new(SNAKE_CASE1).unwrap();
+        assert_eq!(convertor.to_camel(), "snakeCase".to_string());
+        let convertor = NamingPrincipalConvertor::try_new(NONPRINCIPAL_CASE3).unwrap();
+        assert_eq!(convertor.to_snake(), "abc_data".to_string());
+        let err = NamingPrincipalConvertor::try_new(EMPTYCASE).unwrap_err();
+        assert_eq!(err, NpcError::EmptyInput);
+        let err = NamingPrincipalConvertor::try_new("__").unwrap_err();
+        assert_eq!(err, NpcError::UnsupportedInput("__".to_string()));
+        let err = NamingPrincipalConvertor::try_new("!?").unwrap_err();
+        assert_eq!(err, NpcError::UnsupportedInput("!?".to_string()));
+    }
+    #[test]
+    fn test_try_convert() {
+        let param = Parameter::new(CAMEL_CASE, Principal::Snake);
+        assert_eq!(try_convert(&param), Ok("camel_case".to_string()));
+        let param = param.change_principal(Principal::Title);
+        assert_eq!(try_convert(&param), Ok("Camel Case".to_string()));
+        let param = param.change_source("");
+        assert_eq!(try_convert(&param), Err(NpcError::EmptyInput));
+        let param = Parameter::new("hello world!", Principal::Snake);
+        assert_eq!(
+            try_convert(&param),
+            Err(NpcError::InvalidIdentifier {
+                converted: "hello_world!".to_string(),
+                principal: Principal::Snake
+            })
+        );
+        // the characters of a verbatim word are valid only in the verbatim word
+        let param = Parameter::new("x! hello!", Principal::Snake)
+            .add_pre_convert(IgnoreWordsConverter::new(&["x"]).to_convertor());
+        assert_eq!(
+            try_convert(&param),
+            Err(NpcError::InvalidIdentifier {
+                converted: "x!_hello!".to_string(),
+                principal: Principal::Snake
+            })
+        );
+        let param = param.change_source("x! hello");
+        assert_eq!(try_convert(&param), Ok("x!_hello".to_string()));
+    }
+    #[test]
+    fn test_caseless_script() {
+        let convertor = NamingPrincipalConvertor::new("ユーザーID");
+        assert!(convertor.is_camel());
+        assert_eq!(convertor.to_snake(), "ユーザー_id".to_string());
+        assert_eq!(convertor.to_camel(), "ユーザーId".to_string());
+        assert_eq!(convertor.to_pascal(), "ユーザーId".to_string());
+        assert_eq!(convertor.to_constant(), "ユーザー_ID".to_string());
+        assert_eq!(convertor.to_chain(), "ユーザー-id".to_string());
+        let convertor = NamingPrincipalConvertor::new("user名前");
+        assert!(convertor.is_flat());
+        assert_eq!(convertor.to_snake(), "user_名前".to_string());
+        assert_eq!(convertor.to_camel(), "user名前".to_string());
+        assert_eq!(convertor.to_pascal(), "User名前".to_string());
+        assert_eq!(convertor.to_constant(), "USER_名前".to_string());
+        let convertor = NamingPrincipalConvertor::new("ユーザー_名前");
+        assert!(convertor.is_snake());
+        assert_eq!(convertor.to_camel(), "ユーザー名前".to_string());
+
+        let reject = SplitOptions::new().caseless(Caseless::Reject);
+        assert_eq!(
+            NamingPrincipalConvertor::try_with_options("user名前", reject).err(),
+            Some(NpcError::CaselessInput("user名前".to_string()))
+        );
+        assert!(NamingPrincipalConvertor::try_with_options("userName", reject).is_ok());
+        let param = Parameter::new("ユーザーID", Principal::Snake).change_split_options(reject);
+        assert_eq!(
+            try_convert(&param),
+            Err(NpcError::CaselessInput("ユーザーID".to_string()))
+        );
+    }
+    #[test]
+    fn test_rewrite_text() {
+        use convertor::PascalCaseReservedIdentifiersConverter;
+        use language::Language;
+
+        let param = Parameter::new("", Principal::Camel).add_post_convert(
+            PascalCaseReservedIdentifiersConverter::default()
+                .add("GitHub")
+                .to_convertor(),
+        );
+        let text =
+            "def fetch_github_user(user_id):\n    return get('github_user', user_id)  # user_id\n";
+        let options = RewriteOptions::new().language(Language::Python);
+        assert_eq!(
+            rewrite_text(text, &param, options),
+            "def fetchGitHubUser(userId):\n    return get('github_user', userId)  # user_id\n"
+        );
+        assert_eq!(
+            rewrite_text(
+                text,
+                &param,
+                options.include_strings(true).include_comments(true)
+            ),
+            "def fetchGitHubUser(userId):\n    return get('githubUser', userId)  # userId\n"
+        );
+
+        // keywords are kept, and converted keywords are escaped
+        let param = Parameter::new("", Principal::Snake);
+        let options = RewriteOptions::new().language(Language::Rust);
+        assert_eq!(
+            rewrite_text("let Type = _;\n", &param, options),
+            "let r#type = _;\n"
+        );
+        assert_eq!(
+            try_rewrite_text("let Type = _;\n", &param, options),
+            Ok("let r#type = _;\n".to_string())
+        );
+
+        // literals are kept byte for byte with their prefixes, and a raw identifier is one identifier
+        let param = Parameter::new("", Principal::Pascal);
+        let text = r###"let r#myType = r#"has "quoted" userName"# + b"abc" + br"x" + b'y';"###;
+        assert_eq!(
+            rewrite_text(text, &param, options),
+            r###"let MyType = r#"has "quoted" userName"# + b"abc" + br"x" + b'y';"###
+        );
+        assert_eq!(
+            rewrite_text(text, &param, options.include_strings(true)),
+            r###"let MyType = r#"Has "Quoted" UserName"# + b"Abc" + br"X" + b'Y';"###
+        );
+        assert_eq!(
+            rewrite_text("let r#type = r#user_id;", &param, options),
+            "let Type = UserId;"
+        );
+        let param = Parameter::new("", Principal::Snake);
+        assert_eq!(
+            rewrite_text("let r#Type = r#async;", &param, options),
+            "let r#type = r#async;"
+        );
+
+        let param = Parameter::new("", Principal::Camel);
+        let text = "user_id = f\"{user_id}\" + rb'user_id' + '''it's\nuser_id'''\n";
+        assert_eq!(
+            rewrite_text(
+                text,
+                &param,
+                RewriteOptions::new().language(Language::Python)
+            ),
+            "userId = f\"{user_id}\" + rb'user_id' + '''it's\nuser_id'''\n"
+        );
+
+        // struct tags of Go and the string parts of template literals are kept
+        let param = Parameter::new("", Principal::Pascal);
+        let text = "type user struct {\n\tuser_id int `json:\"user_id\"`\n}\n";
+        assert_eq!(
+            rewrite_text(text, &param, RewriteOptions::new().language(Language::Go)),
+            "type User struct {\n\tUserId int `json:\"user_id\"`\n}\n"
+        );
+        let text = "const message = `hello ${user_name}`;\n";
+        assert_eq!(
+            rewrite_text(
+                text,
+                &param,
+                RewriteOptions::new().language(Language::TypeScript)
+            ),
+            "const Message = `hello ${UserName}`;\n"
+        );
+
+        // contextual keywords are kept too
+        let text = "var user_name = user_id as string;\n";
+        assert_eq!(
+            rewrite_text(
+                text,
+                &param,
+                RewriteOptions::new().language(Language::CSharp)
+            ),
+            "var UserName = UserId as string;\n"
+        );
+        let text = "type user_id = string;\n";
+        assert_eq!(
+            rewrite_text(
+                text,
+                &param,
+                RewriteOptions::new().language(Language::TypeScript)
+            ),
+            "type UserId = string;\n"
+        );
+
+        // underscores at the edges are kept, so private names stay private
+        let param = Parameter::new("", Principal::Camel);
+        let text = "def __init__(self, _user_id):\n    self._user_id = __user_name__\n";
+        assert_eq!(
+            rewrite_text(
+                text,
+                &param,
+                RewriteOptions::new().language(Language::Python)
+            ),
+            "def __init__(self, _userId):\n    self._userId = __userName__\n"
+        );
+        let param = Parameter::new("", Principal::Chain);
+        assert_eq!(
+            rewrite_text("_user_id", &param, RewriteOptions::new()),
+            "_user-id"
+        );
+    }
+    #[test]
+    fn test_unicode_case_mapping() {
+        let convertor = NamingPrincipalConvertor::new("ÄpfelBaum");
+        assert_eq!(convertor.to_snake(), "äpfel_baum".to_string());
+        assert_eq!(convertor.to_camel(), "äpfelBaum".to_string());
+        assert_eq!(convertor.to_constant(), "ÄPFEL_BAUM".to_string());
+        // multi characters mapping
+        let convertor = NamingPrincipalConvertor::new("straße_name");
+        assert_eq!(convertor.to_constant(), "STRASSE_NAME".to_string());
+        assert_eq!(convertor.to_pascal(), "StraßeName".to_string());
+        // greek
+        let convertor = NamingPrincipalConvertor::new("ΚαλημέραΚόσμε");
+        assert_eq!(convertor.to_snake(), "καλημέρα_κόσμε".to_string());
+        assert_eq!(convertor.to_constant(), "ΚΑΛΗΜΈΡΑ_ΚΌΣΜΕ".to_string());
+        let convertor = NamingPrincipalConvertor::new("ΟΔΟΣ_ΚΑΙ");
+        assert_eq!(convertor.to_snake(), "οδος_και".to_string());
+        assert_eq!(convertor.to_camel(), "οδοςΚαι".to_string());
+        // cyrillic
+        let convertor = NamingPrincipalConvertor::new("приветМир");
+        assert_eq!(convertor.to_snake(), "привет_мир".to_string());
+        assert_eq!(convertor.to_pascal(), "ПриветМир".to_string());
+        assert_eq!(convertor.to_chain(), "привет-мир".to_string());
+        assert_eq!(convertor.to_constant(), "ПРИВЕТ_МИР".to_string());
+    }
+    #[test]
+    fn test_digit_policy_is_same_for_every_principal() {
+        let options = SplitOptions::new().digit(Digit::Separate);
+        for source in [
+            "vector3D",
+            "Vector3D",
+            "vector3_d",
+            "VECTOR3_D",
+            "vector3-d",
+        ] {
+            let convertor = NamingPrincipalConvertor::with_options(source, options);
+            assert_eq!(convertor.to_snake(), "vector_3_d".to_string());
+            assert_eq!(convertor.to_camel(), "vector3D".to_string());
+            assert_eq!(convertor.to_pascal(), "Vector3D".to_string());
+            assert_eq!(convertor.to_constant(), "VECTOR_3_D".to_string());
+            assert_eq!(convertor.to_chain(), "vector-3-d".to_string());
+        }
+        for source in [
+            "vector3D",
+            "Vector3D",
+            "vector3_d",
+            "VECTOR3_D",
+            "vector3-d",
+        ] {
+            let convertor = NamingPrincipalConvertor::new(source);
+            assert_eq!(convertor.to_snake(), "vector3_d".to_string());
+            assert_eq!(convertor.to_chain(), "vector3-d".to_string());
+        }
+    }
+    #[test]
+    fn test_split_acronym() {
+        let options = SplitOptions::new().acronym(Acronym::Split);
+        let convertor = NamingPrincipalConvertor::with_options(PASCAL_CASE2, options);
+        assert_eq!(convertor.to_snake(), "a_b_c_data".to_string());
+        assert_eq!(convertor.to_chain(), "a-b-c-data".to_string());
+        assert_eq!(convertor.to_constant(), "A_B_C_DATA".to_string());
+        assert_eq!(convertor.to_pascal(), "ABCData".to_string());
+        assert_eq!(convertor.to_camel(), "aBCData".to_string());
+        let convertor = NamingPrincipalConvertor::with_options("HTTPServer", options);
+        assert_eq!(convertor.to_snake(), "h_t_t_p_server".to_string());
+        let convertor = NamingPrincipalConvertor::new("HTTPServer");
+        assert_eq!(convertor.to_snake(), "http_server".to_string());
     }
 }
 
@@ -607,15 +1170,69 @@ enum NamingPrincipal<'a> {
     Chain(&'a str),
     Empty(&'a str),
     Flat(&'a str),
+    UpperFlat(&'a str),
+    Cobol(&'a str),
+    Ada(&'a str),
+    CamelSnake(&'a str),
+    Train(&'a str),
+    Dot(&'a str),
+    Path(&'a str),
+    Title(&'a str),
+    Sentence(&'a str),
     NonPrincipal(&'a str),
 }
 
 impl<'a> NamingPrincipal<'a> {
+    fn source(&self) -> &'a str {
+        match self {
+            Self::Snake(s)
+            | Self::Constant(s)
+            | Self::Camel(s)
+            | Self::Pascal(s)
+            | Self::Chain(s)
+            | Self::Empty(s)
+            | Self::Flat(s)
+            | Self::UpperFlat(s)
+            | Self::Cobol(s)
+            | Self::Ada(s)
+            | Self::CamelSnake(s)
+            | Self::Train(s)
+            | Self::Dot(s)
+            | Self::Path(s)
+            | Self::Title(s)
+            | Self::Sentence(s)
+            | Self::NonPrincipal(s) => s,
+        }
+    }
+    fn principal(&self) -> Option<Principal> {
+        match self {
+            Self::Snake(_) => Some(Principal::Snake),
+            Self::Constant(_) => Some(Principal::Constant),
+            Self::Camel(_) => Some(Principal::Camel),
+            Self::Pascal(_) => Some(Principal::Pascal),
+            Self::Chain(_) => Some(Principal::Chain),
+            Self::Flat(_) => Some(Principal::Flat),
+            Self::UpperFlat(_) => Some(Principal::UpperFlat),
+            Self::Cobol(_) => Some(Principal::Cobol),
+            Self::Ada(_) => Some(Principal::Ada),
+            Self::CamelSnake(_) => Some(Principal::CamelSnake),
+            Self::Train(_) => Some(Principal::Train),
+            Self::Dot(_) => Some(Principal::Dot),
+            Self::Path(_) => Some(Principal::Path),
+            Self::Title(_) => Some(Principal::Title),
+            Self::Sentence(_) => Some(Principal::Sentence),
+            Self::Empty(_) | Self::NonPrincipal(_) => None,
+        }
+    }
     fn new(source: &'a str) -> Self {
         //flat contain camel and snake and chain that's why is_flat is position top
         if is_flat(source) {
             return Self::Flat(source);
         }
+        //upper flat contain constant and cobol as well as flat
+        if is_upper_flat(source) {
+            return Self::UpperFlat(source);
+        }
         if is_camel(source) {
             return Self::Camel(source);
         }
@@ -631,13 +1248,40 @@ impl<'a> NamingPrincipal<'a> {
         if is_chain(source) {
             return Self::Chain(source);
         }
+        if is_cobol(source) {
+            return Self::Cobol(source);
+        }
+        if is_ada(source) {
+            return Self::Ada(source);
+        }
+        if is_camel_snake(source) {
+            return Self::CamelSnake(source);
+        }
+        if is_train(source) {
+            return Self::Train(source);
+        }
+        if is_dot(source) {
+            return Self::Dot(source);
+        }
+        if is_path(source) {
+            return Self::Path(source);
+        }
+        if is_title(source) {
+            return Self::Title(source);
+        }
+        if is_sentence(source) {
+            return Self::Sentence(source);
+        }
         if is_empty(source) {
             return Self::Empty(source);
         }
-        if is_non_principal(source) {
-            return Self::NonPrincipal(source);
+        Self::NonPrincipal(source)
+    }
+    fn try_new(source: &'a str) -> Result<Self, NpcError> {
+        match Self::new(source) {
+            Self::Empty(_) => Err(NpcError::EmptyInput),
+            principal => Ok(principal),
         }
-        panic!("not considering case! have to impl case {}", source)
     }
 }
 
@@ -648,16 +1292,34 @@ fn is_non_principal(source: &str) -> bool {
         || is_chain(source)
         || is_constant(source)
         || is_empty(source)
-        || is_snake(source))
+        || is_snake(source)
+        || is_upper_flat(source)
+        || is_cobol(source)
+        || is_ada(source)
+        || is_camel_snake(source)
+        || is_train(source)
+        || is_dot(source)
+        || is_path(source)
+        || is_title(source)
+        || is_sentence(source))
 }
 fn is_flat(source: &str) -> bool {
-    !is_empty(source) && source.chars().all(|c| c.is_lowercase() || c.is_numeric())
+    !is_empty(source) && source.chars().all(|c| is_lower(c) || c.is_numeric())
+}
+fn is_upper_flat(source: &str) -> bool {
+    !is_empty(source) && source.chars().all(|c| c.is_uppercase() || c.is_numeric())
 }
 fn is_chain(source: &str) -> bool {
     !is_empty(source)
         && source
             .chars()
-            .all(|c| c == '-' || c != '_' && c.is_lowercase() || c.is_numeric())
+            .all(|c| c == '-' || c != '_' && is_lower(c) || c.is_numeric())
+}
+fn is_cobol(source: &str) -> bool {
+    !is_empty(source)
+        && source
+            .chars()
+            .all(|c| c == '-' || c != '_' && c.is_uppercase() || c.is_numeric())
 }
 fn is_constant(source: &str) -> bool {
     !is_empty(source)
@@ -669,10 +1331,10 @@ fn is_snake(source: &str) -> bool {
     !is_empty(source)
         && source
             .chars()
-            .all(|c| c == '_' || c != '-' && c.is_lowercase() || c.is_numeric())
+            .all(|c| c == '_' || c != '-' && is_lower(c) || c.is_numeric())
 }
 fn is_pascal(source: &str) -> bool {
-    if is_empty(source) || source.contains("_") || source.contains("-") {
+    if is_empty(source) || !source.chars().all(|c| c.is_alphanumeric()) {
         return false;
     }
     let first = source.chars().next().unwrap();
@@ -682,15 +1344,73 @@ fn is_pascal(source: &str) -> bool {
     !source.chars().all(|c| c.is_uppercase() || c.is_numeric())
 }
 fn is_camel(source: &str) -> bool {
-    if is_empty(source) || source.contains("_") || source.contains("-") {
+    if is_empty(source) || !source.chars().all(|c| c.is_alphanumeric()) {
         return false;
     }
     if let Some(first) = source.chars().next() {
-        first.is_lowercase()
+        is_lower(first)
     } else {
         false
     }
 }
+fn is_ada(source: &str) -> bool {
+    is_capitalized_words(source, '_', |first| first.is_uppercase())
+}
+fn is_camel_snake(source: &str) -> bool {
+    is_capitalized_words(source, '_', is_lower)
+}
+fn is_train(source: &str) -> bool {
+    is_capitalized_words(source, '-', |first| first.is_uppercase())
+}
+fn is_title(source: &str) -> bool {
+    is_capitalized_words(source, ' ', |first| first.is_uppercase())
+}
+fn is_sentence(source: &str) -> bool {
+    let Some((first, rest)) = source.split_once(' ') else {
+        return is_capitalized_words(source, ' ', |first| first.is_uppercase());
+    };
+    is_capitalized_words(first, ' ', |first| first.is_uppercase())
+        && rest
+            .chars()
+            .all(|c| c == ' ' || is_lower(c) || c.is_numeric())
+}
+fn is_dot(source: &str) -> bool {
+    !is_empty(source)
+        && source
+            .chars()
+            .all(|c| c == '.' || is_lower(c) || c.is_numeric())
+}
+fn is_path(source: &str) -> bool {
+    !is_empty(source)
+        && source
+            .chars()
+            .all(|c| c == '/' || is_lower(c) || c.is_numeric())
+}
+// words split by separator are capitalized except the first word which satisfies is_first_word_head
+fn is_capitalized_words(
+    source: &str,
+    separator: char,
+    is_first_word_head: fn(char) -> bool,
+) -> bool {
+    let mut words = source.split(separator).filter(|w| !w.is_empty()).peekable();
+    if words.peek().is_none() {
+        return false;
+    }
+    words.enumerate().all(|(i, word)| {
+        let mut chars = word.chars();
+        let head = chars.next().unwrap();
+        let is_head = if i == 0 {
+            is_first_word_head(head)
+        } else {
+            head.is_uppercase()
+        };
+        is_head && chars.all(|c| is_lower(c) || c.is_numeric())
+    })
+}
+// caseless letters have no upper case, so they are treated as lower case
+fn is_lower(c: char) -> bool {
+    c.is_lowercase() || is_caseless(c)
+}
 fn is_empty(source: &str) -> bool {
     source.len() == 0
 }
@@ -704,6 +1424,7 @@ mod test_naming_principal {
     fn test_is_nonprincipal_and_new_nonprincipal() {
         assert!(is_non_principal(NONPRINCIPAL_CASE1));
         assert!(is_non_principal(NONPRINCIPAL_CASE2));
+        assert!(is_non_principal(NONPRINCIPAL_CASE3));
         assert!(!is_non_principal(FLATCASE));
         assert!(!is_non_principal(EMPTYCASE));
         assert!(!is_non_principal(CHAIN_CASE1));
@@ -777,7 +1498,7 @@ mod test_naming_principal {
         let np = NamingPrincipal::new(CONSTANT_CASE1);
         assert_eq!(np, NamingPrincipal::Constant(CONSTANT_CASE1));
         let np = NamingPrincipal::new(CONSTANT_CASE2);
-        assert_eq!(np, NamingPrincipal::Constant(CONSTANT_CASE2));
+        assert_eq!(np, NamingPrincipal::UpperFlat(CONSTANT_CASE2));
         let np = NamingPrincipal::new(CONSTANT_CASE3);
         assert_eq!(np, NamingPrincipal::Constant(CONSTANT_CASE3));
     }
@@ -822,6 +1543,134 @@ mod test_naming_principal {
         assert_eq!(np, NamingPrincipal::Pascal(PASCAL_CASE2));
     }
     #[test]
+    fn test_is_upper_flat_and_new_upper_flat() {
+        assert!(is_upper_flat(UPPER_FLATCASE));
+        assert!(is_upper_flat(CONSTANT_CASE2));
+        assert!(!is_upper_flat(CONSTANT_CASE1));
+        assert!(!is_upper_flat(COBOL_CASE));
+        assert!(!is_upper_flat(FLATCASE));
+        assert!(!is_upper_flat(EMPTYCASE));
+        assert!(!is_upper_flat(PASCAL_CASE2));
+        let np = NamingPrincipal::new(UPPER_FLATCASE);
+        assert_eq!(np, NamingPrincipal::UpperFlat(UPPER_FLATCASE));
+    }
+    #[test]
+    fn test_is_cobol_and_new_cobol() {
+        assert!(is_cobol(COBOL_CASE));
+        assert!(is_cobol(UPPER_FLATCASE));
+        assert!(!is_cobol(CONSTANT_CASE1));
+        assert!(!is_cobol(CHAIN_CASE1));
+        assert!(!is_cobol(EMPTYCASE));
+        assert!(!is_cobol(NONPRINCIPAL_CASE2));
+        let np = NamingPrincipal::new(COBOL_CASE);
+        assert_eq!(np, NamingPrincipal::Cobol(COBOL_CASE));
+    }
+    #[test]
+    fn test_is_ada_and_new_ada() {
+        assert!(is_ada(ADA_CASE));
+        assert!(is_ada("_Ada_Case"));
+        assert!(!is_ada(PASCAL_CASE1));
+        assert!(!is_ada(CAMEL_SNAKE_CASE));
+        assert!(!is_ada(CONSTANT_CASE1));
+        assert!(!is_ada(SNAKE_CASE1));
+        assert!(!is_ada(EMPTYCASE));
+        assert!(!is_ada("__"));
+        assert!(!is_ada(NONPRINCIPAL_CASE1));
+        let np = NamingPrincipal::new(ADA_CASE);
+        assert_eq!(np, NamingPrincipal::Ada(ADA_CASE));
+    }
+    #[test]
+    fn test_is_camel_snake_and_new_camel_snake() {
+        assert!(is_camel_snake(CAMEL_SNAKE_CASE));
+        assert!(!is_camel_snake(ADA_CASE));
+        assert!(!is_camel_snake(SNAKE_CASE1));
+        assert!(!is_camel_snake(CAMEL_CASE));
+        assert!(!is_camel_snake(EMPTYCASE));
+        assert!(!is_camel_snake(NONPRINCIPAL_CASE1));
+        let np = NamingPrincipal::new(CAMEL_SNAKE_CASE);
+        assert_eq!(np, NamingPrincipal::CamelSnake(CAMEL_SNAKE_CASE));
+    }
+    #[test]
+    fn test_is_train_and_new_train() {
+        assert!(is_train("Http-Header-Case"));
+        assert!(!is_train(CHAIN_CASE1));
+        assert!(!is_train(COBOL_CASE));
+        assert!(!is_train(ADA_CASE));
+        assert!(!is_train(EMPTYCASE));
+        let np = NamingPrincipal::new("Http-Header-Case");
+        assert_eq!(np, NamingPrincipal::Train("Http-Header-Case"));
+    }
+    #[test]
+    fn test_is_dot_and_is_path() {
+        assert!(is_dot("dot.case"));
+        assert!(is_dot(FLATCASE));
+        assert!(!is_dot("Dot.Case"));
+        assert!(!is_dot(SNAKE_CASE1));
+        assert!(!is_dot(EMPTYCASE));
+        assert!(is_path("path/case"));
+        assert!(!is_path("dot.case"));
+        assert!(!is_path(EMPTYCASE));
+        assert_eq!(
+            NamingPrincipal::new("dot.case"),
+            NamingPrincipal::Dot("dot.case")
+        );
+        assert_eq!(
+            NamingPrincipal::new("path/case"),
+            NamingPrincipal::Path("path/case")
+        );
+    }
+    #[test]
+    fn test_is_title_and_is_sentence() {
+        assert!(is_title("Title Case"));
+        assert!(!is_title("Sentence case"));
+        assert!(!is_title(EMPTYCASE));
+        assert!(is_sentence("Sentence case"));
+        assert!(!is_sentence("Title Case"));
+        assert!(!is_sentence("sentence case"));
+        assert!(!is_sentence(EMPTYCASE));
+        assert_eq!(
+            NamingPrincipal::new("Title Case"),
+            NamingPrincipal::Title("Title Case")
+        );
+        assert_eq!(
+            NamingPrincipal::new("Sentence case"),
+            NamingPrincipal::Sentence("Sentence case")
+        );
+    }
+    #[test]
+    fn test_detect() {
+        let detection = detect(FLATCASE);
+        assert_eq!(detection.best(), Some(Principal::Flat));
+        assert_eq!(
+            detection.candidates(),
+            &[
+                Principal::Camel,
+                Principal::Snake,
+                Principal::Chain,
+                Principal::Dot,
+                Principal::Path,
+                Principal::Flat,
+                Principal::CamelSnake
+            ]
+        );
+        assert!(detection.is_ambiguous());
+        assert_eq!(detection.confidence(), Confidence::Ambiguous);
+
+        let detection = detect(SNAKE_CASE1);
+        assert_eq!(detection.best(), Some(Principal::Snake));
+        assert_eq!(detection.candidates(), &[Principal::Snake]);
+        assert_eq!(detection.confidence(), Confidence::Certain);
+
+        let detection = detect(NONPRINCIPAL_CASE2);
+        assert_eq!(detection.best(), None);
+        assert!(detection.candidates().is_empty());
+        assert_eq!(detection.confidence(), Confidence::Unknown);
+
+        let detection = detect(EMPTYCASE);
+        assert_eq!(detection.best(), None);
+        assert_eq!(detection.confidence(), Confidence::Unknown);
+    }
+    #[test]
     fn test_is_camel_and_new_camel() {
         assert!(is_camel(CAMEL_CASE));
         assert!(is_camel(FLATCASE));
@@ -0,0 +1,257 @@
+use crate::{
+    transliteration::transliterate,
+    word::{is_separator, map_unmasked, split_words},
+    PreConvert, Principal,
+};
+
+/// StripPrefixConverter removes a prefix like `m_` or `I` of interfaces before the conversion.
+///
+/// The prefix is removed only if it ends at a word boundary, so `IUserRepository` loses `I` but `Item` does not.
+pub struct StripPrefixConverter {
+    inner: Vec<String>,
+}
+
+impl StripPrefixConverter {
+    pub fn new(inner: &[&str]) -> Self {
+        Self {
+            inner: inner.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+    pub fn to_convertor(self) -> Box<Self> {
+        Box::new(self)
+    }
+    #[allow(clippy::should_implement_trait)]
+    pub fn add(mut self, prefix: impl Into<String>) -> Self {
+        self.inner.push(prefix.into());
+        self
+    }
+}
+
+impl PreConvert for StripPrefixConverter {
+    fn convert(&self, source: &str, _: Principal) -> String {
+        for prefix in self.inner.iter().filter(|p| !p.is_empty()) {
+            let Some(rest) = source.strip_prefix(prefix.as_str()) else {
+                continue;
+            };
+            if has_word(rest) && is_boundary(source, source.len() - rest.len()) {
+                return rest.trim_start_matches(is_separator).to_string();
+            }
+        }
+        source.to_string()
+    }
+}
+
+/// StripSuffixConverter removes a suffix like `_t` or `Impl` before the conversion.
+///
+/// The suffix is removed only if it starts at a word boundary, so `UserImpl` loses `Impl` but `Simple` does not lose `le`.
+pub struct StripSuffixConverter {
+    inner: Vec<String>,
+}
+
+impl StripSuffixConverter {
+    pub fn new(inner: &[&str]) -> Self {
+        Self {
+            inner: inner.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+    pub fn to_convertor(self) -> Box<Self> {
+        Box::new(self)
+    }
+    #[allow(clippy::should_implement_trait)]
+    pub fn add(mut self, suffix: impl Into<String>) -> Self {
+        self.inner.push(suffix.into());
+        self
+    }
+}
+
+impl PreConvert for StripSuffixConverter {
+    fn convert(&self, source: &str, _: Principal) -> String {
+        for suffix in self.inner.iter().filter(|s| !s.is_empty()) {
+            let Some(rest) = source.strip_suffix(suffix.as_str()) else {
+                continue;
+            };
+            if has_word(rest) && is_boundary(source, rest.len()) {
+                return rest.trim_end_matches(is_separator).to_string();
+            }
+        }
+        source.to_string()
+    }
+}
+
+fn has_word(source: &str) -> bool {
+    !split_words(source).is_empty()
+}
+
+// whether index of source is next to a separator or the start of a word
+fn is_boundary(source: &str, index: usize) -> bool {
+    source[..index].ends_with(is_separator)
+        || source[index..].starts_with(is_separator)
+        || split_words(source).iter().any(|w| w.start() == index)
+}
+
+/// TrimConverter removes white spaces and separators at the head and the tail of the source.
+///
+/// `"  _user_name_ "` -> `"user_name"`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TrimConverter;
+
+impl TrimConverter {
+    pub fn new() -> Self {
+        Self
+    }
+    pub fn to_convertor(self) -> Box<Self> {
+        Box::new(self)
+    }
+}
+
+impl PreConvert for TrimConverter {
+    fn convert(&self, source: &str, _: Principal) -> String {
+        source.trim_matches(is_separator).to_string()
+    }
+}
+
+/// CollapseSeparatorsConverter replaces a run of separators with its first separator.
+///
+/// `"__user--name__"` -> `"_user-name_"`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CollapseSeparatorsConverter;
+
+impl CollapseSeparatorsConverter {
+    pub fn new() -> Self {
+        Self
+    }
+    pub fn to_convertor(self) -> Box<Self> {
+        Box::new(self)
+    }
+}
+
+impl PreConvert for CollapseSeparatorsConverter {
+    fn convert(&self, source: &str, _: Principal) -> String {
+        map_unmasked(source, |part| {
+            let mut result = String::with_capacity(part.len());
+            let mut prev_is_separator = false;
+            for c in part.chars() {
+                let is_sep = is_separator(c);
+                if !(is_sep && prev_is_separator) {
+                    result.push(c);
+                }
+                prev_is_separator = is_sep;
+            }
+            result
+        })
+    }
+}
+
+/// TransliterateConverter replaces non-ASCII letters with ASCII ones before the conversion.
+///
+/// `"café_crème"` -> `"cafe_creme"`, `"Straße"` -> `"Strasse"`, and `"ユーザー"` -> `"yuza"` with `romanize_kana`.
+/// Characters without transliteration, like kanji, are kept, so use `CharacterSet::Ascii` of the corrector to remove them.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TransliterateConverter {
+    kana: bool,
+}
+
+impl TransliterateConverter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn to_convertor(self) -> Box<Self> {
+        Box::new(self)
+    }
+    /// romanize hiragana and katakana too
+    pub fn romanize_kana(mut self) -> Self {
+        self.kana = true;
+        self
+    }
+}
+
+impl PreConvert for TransliterateConverter {
+    fn convert(&self, source: &str, _: Principal) -> String {
+        map_unmasked(source, |part| transliterate(part, self.kana))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{convert, corrector::InvalidCharacterCorrector, Parameter, Principal};
+
+    #[test]
+    fn strip_prefix_at_word_boundary() {
+        let sut = StripPrefixConverter::new(&["m_", "I"]);
+        assert_eq!(sut.convert("m_count", Principal::Snake), "count");
+        assert_eq!(
+            sut.convert("IUserRepository", Principal::Snake),
+            "UserRepository"
+        );
+        assert_eq!(sut.convert("Item", Principal::Snake), "Item");
+        assert_eq!(sut.convert("IOError", Principal::Snake), "IOError");
+        assert_eq!(sut.convert("I", Principal::Snake), "I");
+        assert_eq!(sut.convert("m_", Principal::Snake), "m_");
+
+        let sut = StripPrefixConverter::new(&["m"]);
+        assert_eq!(sut.convert("m_count", Principal::Snake), "count");
+        assert_eq!(sut.convert("mCount", Principal::Snake), "Count");
+        assert_eq!(sut.convert("model", Principal::Snake), "model");
+    }
+    #[test]
+    fn strip_suffix_at_word_boundary() {
+        let sut = StripSuffixConverter::new(&["_t", "Impl"]);
+        assert_eq!(sut.convert("size_t", Principal::Snake), "size");
+        assert_eq!(sut.convert("UserImpl", Principal::Snake), "User");
+        assert_eq!(sut.convert("Simpl", Principal::Snake), "Simpl");
+        assert_eq!(sut.convert("_t", Principal::Snake), "_t");
+
+        let sut = StripSuffixConverter::new(&["le"]);
+        assert_eq!(sut.convert("Simple", Principal::Snake), "Simple");
+    }
+    #[test]
+    fn trim_and_collapse_separators() {
+        let sut = TrimConverter::new();
+        assert_eq!(sut.convert("  _user_name_ ", Principal::Snake), "user_name");
+        assert_eq!(sut.convert("__", Principal::Snake), "");
+
+        let sut = CollapseSeparatorsConverter::new();
+        assert_eq!(
+            sut.convert("__user--name__", Principal::Snake),
+            "_user-name_"
+        );
+        assert_eq!(sut.convert("a _ b", Principal::Snake), "a b");
+    }
+    #[test]
+    fn pre_convert_before_conversion() {
+        let param = Parameter::new("  m_user_count_t ", Principal::Pascal)
+            .add_pre_convert(TrimConverter::new().to_convertor())
+            .add_pre_convert(StripPrefixConverter::new(&["m_"]).to_convertor())
+            .add_pre_convert(StripSuffixConverter::new(&["_t"]).to_convertor());
+        assert_eq!(convert(&param), "UserCount");
+
+        let param = Parameter::new("__init__", Principal::Snake)
+            .add_pre_convert(CollapseSeparatorsConverter::new().to_convertor());
+        assert_eq!(convert(&param), "_init_");
+
+        let param = Parameter::new("invalid:identifier@", Principal::Camel)
+            .add_pre_convert(InvalidCharacterCorrector::default().to_convertor());
+        assert_eq!(convert(&param), "invalidIdentifier");
+    }
+    #[test]
+    fn transliterate_to_ascii() {
+        let param = |source: &str, to: Principal| {
+            Parameter::new(source, to).add_pre_convert(TransliterateConverter::new().to_convertor())
+        };
+        assert_eq!(
+            convert(&param("café_crème", Principal::Snake)),
+            "cafe_creme"
+        );
+        assert_eq!(convert(&param("Straße", Principal::Snake)), "strasse");
+        assert_eq!(convert(&param("Straße", Principal::Constant)), "STRASSE");
+        assert_eq!(
+            convert(&param("ユーザーID", Principal::Snake)),
+            "ユーザー_id"
+        );
+
+        let param = Parameter::new("ユーザーID", Principal::Pascal)
+            .add_pre_convert(TransliterateConverter::new().romanize_kana().to_convertor());
+        assert_eq!(convert(&param), "YuzaId");
+    }
+}
@@ -0,0 +1,487 @@
+use std::ops::Range;
+
+use crate::language::Language;
+
+/// RewriteOptions is the rule of `rewrite_text` which parts of the text are rewritten.
+///
+/// String literals and comments are kept as they are by default.
+/// Without a language, `//`, `#` and `/* */` are comments, and `"` and `'` enclose strings
+/// with the prefixes and the raw or triple quoted strings of both Rust and Python, like `b"..."` or `r#"..."#`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RewriteOptions {
+    strings: bool,
+    comments: bool,
+    language: Option<Language>,
+}
+
+impl RewriteOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// rewrite identifiers in string literals too
+    pub fn include_strings(mut self, include: bool) -> Self {
+        self.strings = include;
+        self
+    }
+    /// rewrite identifiers in comments too
+    pub fn include_comments(mut self, include: bool) -> Self {
+        self.comments = include;
+        self
+    }
+    /// use the comment syntax and keywords of the language
+    pub fn language(mut self, language: Language) -> Self {
+        self.language = Some(language);
+        self
+    }
+    pub(crate) fn get_language(&self) -> Option<Language> {
+        self.language
+    }
+}
+
+/// identifier_spans returns the byte ranges of identifiers in the text to rewrite.
+///
+/// An identifier is a run of alphanumeric characters or `_` which does not start with a digit,
+/// or a raw identifier like `r#type` of Rust.
+/// The prefix of a string literal, like `b` of `b"..."`, is a part of the literal and not an identifier.
+/// The expressions of a template literal of TypeScript, like `user` of `` `${user}` ``, are code and not a string.
+pub(crate) fn identifier_spans(text: &str, options: &RewriteOptions) -> Vec<Range<usize>> {
+    let (line_comments, block_comment) = match options.language {
+        Some(language) => (language.line_comments(), language.block_comment()),
+        None => (&["//", "#"][..], Some(("/*", "*/"))),
+    };
+    let language = options.language;
+    let nested = language.is_some_and(|l| l.nests_block_comments());
+    let mut spans = Vec::new();
+    let mut i = 0;
+    while let Some(c) = text[i..].chars().next() {
+        let rest = &text[i..];
+        if let Some(marker) = line_comments.iter().find(|m| rest.starts_with(*m)) {
+            let end = rest.find('\n').map_or(text.len(), |e| i + e);
+            if options.comments {
+                push_identifiers(text, i + marker.len()..end, &mut spans);
+            }
+            i = end;
+            continue;
+        }
+        if let Some((open, close)) = block_comment.filter(|(open, _)| rest.starts_with(open)) {
+            let end = block_comment_end(text, i, (open, close), nested);
+            if options.comments {
+                push_identifiers(text, i + open.len()..end, &mut spans);
+            }
+            i = end;
+            continue;
+        }
+        if c == '`' && language == Some(Language::TypeScript) {
+            i = template_literal(text, i, options, &mut spans);
+            continue;
+        }
+        if let Some((content, end)) = string_literal(text, i, language) {
+            if options.strings {
+                push_identifiers(text, content, &mut spans);
+            }
+            i = end;
+            continue;
+        }
+        if is_identifier_char(c) {
+            let end = rest
+                .find(|c| !is_identifier_char(c))
+                .map_or(text.len(), |e| i + e);
+            if let Some((content, literal_end)) = prefixed_string_literal(text, i, end, language) {
+                if options.strings {
+                    push_identifiers(text, content, &mut spans);
+                }
+                i = literal_end;
+                continue;
+            }
+            if let Some(raw_end) = raw_identifier_end(text, i, end, language) {
+                spans.push(i..raw_end);
+                i = raw_end;
+                continue;
+            }
+            push_identifiers(text, i..end, &mut spans);
+            i = end;
+            continue;
+        }
+        i += c.len_utf8();
+    }
+    spans
+}
+
+// the end of the block comment which starts at start, or the end of the text if it is not closed
+fn block_comment_end(text: &str, start: usize, (open, close): (&str, &str), nested: bool) -> usize {
+    let mut depth = 0;
+    let mut i = start + open.len();
+    while let Some(c) = text[i..].chars().next() {
+        if nested && text[i..].starts_with(open) {
+            depth += 1;
+            i += open.len();
+        } else if text[i..].starts_with(close) {
+            if depth == 0 {
+                return i + close.len();
+            }
+            depth -= 1;
+            i += close.len();
+        } else {
+            i += c.len_utf8();
+        }
+    }
+    text.len()
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+// push runs of identifier characters in the range, skipping the ones which start with a digit like `10u32`
+fn push_identifiers(text: &str, range: Range<usize>, spans: &mut Vec<Range<usize>>) {
+    let mut start = None;
+    for (i, c) in text[range.clone()]
+        .char_indices()
+        .map(|(i, c)| (range.start + i, c))
+        .chain(std::iter::once((range.end, ' ')))
+    {
+        match (start, is_identifier_char(c)) {
+            (None, true) => start = Some(i),
+            (Some(s), false) => {
+                if !text[s..].starts_with(|c: char| c.is_numeric()) {
+                    spans.push(s..i);
+                }
+                start = None;
+            }
+            _ => {}
+        }
+    }
+}
+
+// the content and the end of the string literal which starts at start, None if it is not a string literal
+//
+// A triple quoted string, like a docstring of Python or a text block of Java, may have quotes and new lines in it,
+// and so may a raw string of Go quoted with `` ` ``.
+fn string_literal(
+    text: &str,
+    start: usize,
+    language: Option<Language>,
+) -> Option<(Range<usize>, usize)> {
+    let quote = text[start..].chars().next()?;
+    if quote == '`' && language == Some(Language::Go) {
+        let open = start + 1;
+        return Some(match text[open..].find('`') {
+            Some(e) => (open..open + e, open + e + 1),
+            None => (open..text.len(), text.len()),
+        });
+    }
+    if quote != '"' && quote != '\'' {
+        return None;
+    }
+    let triple = match (quote, language) {
+        (
+            '"',
+            None | Some(Language::Python | Language::Java | Language::Kotlin | Language::Swift),
+        ) => Some("\"\"\""),
+        ('\'', None | Some(Language::Python)) => Some("'''"),
+        _ => None,
+    };
+    if let Some(triple) = triple.filter(|t| text[start..].starts_with(t)) {
+        // a raw string of Kotlin has no escape
+        let escapes = language != Some(Language::Kotlin);
+        let open = start + triple.len();
+        let mut escaped = false;
+        for (i, c) in text[open..].char_indices() {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' if escapes => escaped = true,
+                _ if text[open + i..].starts_with(triple) => {
+                    return Some((open..open + i, open + i + triple.len()));
+                }
+                _ => {}
+            }
+        }
+        return Some((open..text.len(), text.len()));
+    }
+    let end = string_end(text, start, quote, language == Some(Language::Rust))?;
+    Some((start + 1..end - 1, end))
+}
+
+// push the identifiers of the template literal of TypeScript which starts at start, and return the end of it
+//
+// The expressions in `${...}` are code, and the rest is a string literal.
+fn template_literal(
+    text: &str,
+    start: usize,
+    options: &RewriteOptions,
+    spans: &mut Vec<Range<usize>>,
+) -> usize {
+    let mut literal_start = start + 1;
+    let mut escaped = false;
+    let mut i = literal_start;
+    while let Some(c) = text[i..].chars().next() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '`' => {
+                if options.strings {
+                    push_identifiers(text, literal_start..i, spans);
+                }
+                return i + 1;
+            }
+            '$' if text[i..].starts_with("${") => {
+                if options.strings {
+                    push_identifiers(text, literal_start..i, spans);
+                }
+                let open = i + 2;
+                let close = closing_brace(text, open);
+                spans.extend(
+                    identifier_spans(&text[open..close], options)
+                        .into_iter()
+                        .map(|r| open + r.start..open + r.end),
+                );
+                i = (close + 1).min(text.len());
+                literal_start = i;
+                continue;
+            }
+            _ => {}
+        }
+        i += c.len_utf8();
+    }
+    if options.strings {
+        push_identifiers(text, literal_start..text.len(), spans);
+    }
+    text.len()
+}
+
+// the index of `}` which closes the brace opened before open, or the end of the text
+fn closing_brace(text: &str, open: usize) -> usize {
+    let mut depth = 0;
+    for (i, c) in text[open..].char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' if depth == 0 => return open + i,
+            '}' => depth -= 1,
+            _ => {}
+        }
+    }
+    text.len()
+}
+
+// the content and the end of the string literal whose prefix, like `b` of `b"..."`, is from start to prefix_end,
+// None if it is not a prefix of a string literal of the language
+//
+// Without a language, the prefixes of both Rust and Python are string prefixes.
+fn prefixed_string_literal(
+    text: &str,
+    start: usize,
+    prefix_end: usize,
+    language: Option<Language>,
+) -> Option<(Range<usize>, usize)> {
+    let prefix = &text[start..prefix_end];
+    let after = &text[prefix_end..];
+    let rust = matches!(language, Some(Language::Rust) | None)
+        && matches!(prefix, "b" | "r" | "br" | "c" | "cr");
+    let python = matches!(language, Some(Language::Python) | None)
+        && matches!(
+            prefix.to_ascii_lowercase().as_str(),
+            "r" | "u" | "f" | "b" | "t" | "br" | "rb" | "fr" | "rf" | "tr" | "rt"
+        );
+    if rust && prefix.ends_with('r') {
+        // a raw string has no escape, and is closed by `"` with as many `#` as it is opened with
+        let hashes = after.len() - after.trim_start_matches('#').len();
+        if after[hashes..].starts_with('"') {
+            let open = prefix_end + hashes + 1;
+            let close = format!("\"{}", "#".repeat(hashes));
+            return Some(match text[open..].find(&close) {
+                Some(e) => (open..open + e, open + e + close.len()),
+                None => (open..text.len(), text.len()),
+            });
+        }
+    }
+    // `'` of Rust follows only `b`, like `b'a'`
+    if (rust && (after.starts_with('"') || prefix == "b")) || python {
+        return string_literal(text, prefix_end, language);
+    }
+    None
+}
+
+// the end of the raw identifier of Rust like `r#type`, whose `r` is from start to prefix_end
+fn raw_identifier_end(
+    text: &str,
+    start: usize,
+    prefix_end: usize,
+    language: Option<Language>,
+) -> Option<usize> {
+    if language != Some(Language::Rust) || &text[start..prefix_end] != "r" {
+        return None;
+    }
+    let identifier = text[prefix_end..].strip_prefix('#')?;
+    let len = identifier
+        .find(|c| !is_identifier_char(c))
+        .unwrap_or(identifier.len());
+    (len > 0 && !identifier.starts_with(|c: char| c.is_numeric())).then_some(prefix_end + 1 + len)
+}
+
+// the end of the string literal which starts with the quote at start, None if it is not a string literal
+//
+// `'` of Rust is a char literal like `'a'` or `'\n'`, otherwise a lifetime like `'a`.
+// `'` of the other languages has to be closed in the same line, so `don't` in a text is not a string.
+fn string_end(text: &str, start: usize, quote: char, rust: bool) -> Option<usize> {
+    let body = &text[start + 1..];
+    if quote == '\'' && rust {
+        let first = body.chars().next()?;
+        if first == '\\' {
+            return body.get(2..)?.find('\'').map(|e| start + 1 + 2 + e + 1);
+        }
+        let after = first.len_utf8();
+        return body[after..]
+            .starts_with('\'')
+            .then_some(start + 1 + after + 1);
+    }
+    let mut escaped = false;
+    for (i, c) in body.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '\n' if quote == '\'' => return None,
+            _ if c == quote => return Some(start + 1 + i + 1),
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identifiers<'a>(text: &'a str, options: &RewriteOptions) -> Vec<&'a str> {
+        identifier_spans(text, options)
+            .into_iter()
+            .map(|r| &text[r])
+            .collect()
+    }
+    #[test]
+    fn skip_strings_and_comments_by_default() {
+        let text = "let userName = \"user name\"; // the userName\n/* block\ncomment */ # hash\nx";
+        assert_eq!(
+            identifiers(text, &RewriteOptions::new()),
+            vec!["let", "userName", "x"]
+        );
+        assert_eq!(
+            identifiers(text, &RewriteOptions::new().include_strings(true)),
+            vec!["let", "userName", "user", "name", "x"]
+        );
+        assert_eq!(
+            identifiers(text, &RewriteOptions::new().include_comments(true)),
+            vec!["let", "userName", "the", "userName", "block", "comment", "hash", "x"]
+        );
+        assert_eq!(
+            identifiers("a = 10u32 + \"esc\\\"aped\" + b", &RewriteOptions::new()),
+            vec!["a", "b"]
+        );
+        // prefixes of both Rust and Python are a part of string literals
+        assert_eq!(
+            identifiers(
+                "a = b\"c\" + f'd' + br#\"e\"# + \"\"\"g\"h\"\"\"",
+                &RewriteOptions::new()
+            ),
+            vec!["a"]
+        );
+    }
+    #[test]
+    fn use_syntax_of_the_language() {
+        let rust = RewriteOptions::new().language(Language::Rust);
+        assert_eq!(
+            identifiers("fn f<'a>(x: &'a str) -> char { 'x' }", &rust),
+            vec!["fn", "f", "a", "x", "a", "str", "char"]
+        );
+        assert_eq!(
+            identifiers("let r#type = '\\'';", &rust),
+            vec!["let", "r#type"]
+        );
+        // block comments are nested
+        let text = "/* a /* b */ still_comment */ x /* y";
+        assert_eq!(identifiers(text, &rust), vec!["x"]);
+        assert_eq!(
+            identifiers(text, &rust.include_comments(true)),
+            vec!["a", "b", "still_comment", "x", "y"]
+        );
+        // prefixes are a part of string literals, and a raw string is closed by as many `#`
+        let text = r###"r#"a "b" c"# + r##"d"#e"## + b"f" + br"g" + b'h' + c"i" + r"j\" + k"###;
+        assert_eq!(identifiers(text, &rust), vec!["k"]);
+        assert_eq!(
+            identifiers(text, &rust.include_strings(true)),
+            vec!["a", "b", "c", "d", "e", "f", "g", "h", "i", "j", "k"]
+        );
+        assert_eq!(identifiers("r\"unclosed", &rust), Vec::<&str>::new());
+
+        let python = RewriteOptions::new().language(Language::Python);
+        assert_eq!(
+            identifiers("user_id = 'id' # userId // x", &python),
+            vec!["user_id"]
+        );
+        let text = "f\"{a}\" + rb'b' + U\"c\" + Rf'd' + \"\"\"e \"f\"\nit's\"\"\" + '''g\n''' + h";
+        assert_eq!(identifiers(text, &python), vec!["h"]);
+        assert_eq!(
+            identifiers(text, &python.include_strings(true)),
+            vec!["a", "b", "c", "d", "e", "f", "it", "s", "g", "h"]
+        );
+        // prefixes of the other languages are identifiers
+        let go = RewriteOptions::new().language(Language::Go);
+        assert_eq!(identifiers("f(b\"x\")", &go), vec!["f", "b"]);
+        // raw strings of Go, like struct tags
+        let text = "type User struct {\n\tUserID int `json:\"user_id\"\n`\n}";
+        assert_eq!(
+            identifiers(text, &go),
+            vec!["type", "User", "struct", "UserID", "int"]
+        );
+        assert_eq!(
+            identifiers(text, &go.include_strings(true)),
+            vec!["type", "User", "struct", "UserID", "int", "json", "user_id"]
+        );
+
+        // only the expressions of template literals are code
+        let typescript = RewriteOptions::new().language(Language::TypeScript);
+        let text = "`hello ${user_name} and ${a ? `${b}c\\`` : {d: e}.d}!` + f";
+        assert_eq!(
+            identifiers(text, &typescript),
+            vec!["user_name", "a", "b", "d", "e", "d", "f"]
+        );
+        assert_eq!(
+            identifiers(text, &typescript.include_strings(true)),
+            vec![
+                "hello",
+                "user_name",
+                "and",
+                "a",
+                "b",
+                "c",
+                "d",
+                "e",
+                "d",
+                "f"
+            ]
+        );
+        assert_eq!(identifiers("`${unclosed", &typescript), vec!["unclosed"]);
+
+        // text blocks, and raw strings of Kotlin have no escape
+        let java = RewriteOptions::new().language(Language::Java);
+        let text = "String s = \"\"\"\n  say \"hi\" to \\\"\"\"userName\n  \"\"\"; x";
+        assert_eq!(identifiers(text, &java), vec!["String", "s", "x"]);
+        let kotlin = RewriteOptions::new().language(Language::Kotlin);
+        let text = "val s = \"\"\"C:\\dir\\\"\"\"; x";
+        assert_eq!(identifiers(text, &kotlin), vec!["val", "s", "x"]);
+        let swift = RewriteOptions::new().language(Language::Swift);
+        let text = "let s = \"\"\"\nhe said \"hi\"\n\"\"\"; x";
+        assert_eq!(identifiers(text, &swift), vec!["let", "s", "x"]);
+
+        // block comments of Kotlin and Swift are nested too, but not the ones of Java
+        let text = "/* a /* b */ still_comment */ x";
+        assert_eq!(identifiers(text, &kotlin), vec!["x"]);
+        assert_eq!(identifiers(text, &swift), vec!["x"]);
+        assert_eq!(identifiers(text, &java), vec!["still_comment", "x"]);
+
+        let sql = RewriteOptions::new().language(Language::Sql);
+        assert_eq!(
+            identifiers("select userName -- comment\nfrom users", &sql),
+            vec!["select", "userName", "from", "users"]
+        );
+    }
+}
@@ -0,0 +1,251 @@
+use unicode_normalization::{
+    char::{decompose_compatible, is_combining_mark},
+    UnicodeNormalization,
+};
+
+/// transliterate replaces non-ASCII letters with ASCII ones with the built-in tables.
+///
+/// Accents are removed, `café` -> `cafe`, and ligatures are spelled out, `Straße` -> `Strasse`.
+/// Kana is romanized too if kana is true, `ユーザー` -> `yuza`.
+/// The characters which can not be transliterated, like kanji or emoji, are kept as they are.
+pub(crate) fn transliterate(source: &str, kana: bool) -> String {
+    let source = if kana {
+        // compose half-width kana and voiced marks first, `ｶﾞ` -> `ガ`
+        romanize_kana(&source.nfkc().collect::<String>())
+    } else {
+        source.to_string()
+    };
+    let chars = source.chars().collect::<Vec<_>>();
+    let mut result = String::with_capacity(source.len());
+    for (i, c) in chars.iter().copied().enumerate() {
+        if c.is_ascii() {
+            result.push(c);
+            continue;
+        }
+        if let Some(spelled) = spell_out(c) {
+            let next_is_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            if !c.is_uppercase() {
+                result.push_str(spelled);
+            } else if next_is_lower {
+                // `Æther` -> `Aether`
+                result.push_str(&spelled[..1].to_ascii_uppercase());
+                result.push_str(&spelled[1..]);
+            } else {
+                result.push_str(&spelled.to_ascii_uppercase());
+            }
+            continue;
+        }
+        let mut base = String::new();
+        decompose_compatible(c, |d| {
+            if !is_combining_mark(d) {
+                base.push(d)
+            }
+        });
+        if !base.is_empty() && base.is_ascii() {
+            result.push_str(&base);
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+// letters which are not decomposed into ASCII, keyed by the lower case
+fn spell_out(c: char) -> Option<&'static str> {
+    let spelled = match c.to_lowercase().next()? {
+        'ß' => "ss",
+        'æ' => "ae",
+        'œ' => "oe",
+        'ø' => "o",
+        'đ' | 'ð' => "d",
+        'þ' => "th",
+        'ł' => "l",
+        'ı' => "i",
+        'ħ' => "h",
+        'ŧ' => "t",
+        'ŋ' => "ng",
+        _ => return None,
+    };
+    Some(spelled)
+}
+
+/// romanize_kana replaces hiragana and katakana with Hepburn romaji.
+///
+/// The long vowel mark is dropped, so `ユーザー` is `yuza`, not `yuuzaa`.
+fn romanize_kana(source: &str) -> String {
+    let mut result = String::with_capacity(source.len());
+    // start of the romaji of the last kana in result, small kana after it modify it
+    let mut last = None;
+    let mut sokuon = false;
+    for c in source.chars() {
+        let Some(kana) = to_hiragana(c) else {
+            if c == 'ー' && last.is_some() {
+                continue;
+            }
+            last = None;
+            sokuon = false;
+            result.push(c);
+            continue;
+        };
+        match (kana, last) {
+            ('っ', _) => {
+                last = None;
+                sokuon = true;
+            }
+            ('ゃ' | 'ゅ' | 'ょ', Some(start))
+                if result.len() - start > 1 && result.ends_with('i') =>
+            {
+                // `きゃ` -> `kya`, `しゃ` -> `sha`, `じゃ` -> `ja`
+                result.pop();
+                let vowel = &syllable(kana)[1..];
+                if !(result.ends_with('h') || result.ends_with('j')) {
+                    result.push('y');
+                }
+                result.push_str(vowel);
+            }
+            ('ぁ' | 'ぃ' | 'ぅ' | 'ぇ' | 'ぉ', Some(start)) => {
+                // `ファ` -> `fa`, `ティ` -> `ti`, `ウィ` -> `wi`
+                let stem = result[start..].trim_end_matches(['a', 'i', 'u', 'e', 'o']);
+                let stem = match (stem, &result[start..]) {
+                    ("", "u") => "w".to_string(),
+                    ("", "i") => "y".to_string(),
+                    ("", syllable) => syllable.to_string(),
+                    (stem, _) => stem.to_string(),
+                };
+                result.truncate(start);
+                result.push_str(&stem);
+                result.push_str(syllable(kana));
+            }
+            _ => {
+                let romaji = syllable(kana);
+                if sokuon && !romaji.starts_with(['a', 'i', 'u', 'e', 'o', 'n']) {
+                    // `マッチ` -> `matchi`, `ロック` -> `rokku`
+                    result.push(if romaji.starts_with("ch") {
+                        't'
+                    } else {
+                        romaji.as_bytes()[0] as char
+                    });
+                }
+                sokuon = false;
+                last = Some(result.len());
+                result.push_str(romaji);
+            }
+        }
+    }
+    result
+}
+
+fn to_hiragana(c: char) -> Option<char> {
+    match c {
+        'ぁ'..='ゖ' => Some(c),
+        'ァ'..='ヶ' => char::from_u32(c as u32 - 0x60),
+        _ => None,
+    }
+}
+
+fn syllable(hiragana: char) -> &'static str {
+    match hiragana {
+        'あ' | 'ぁ' => "a",
+        'い' | 'ぃ' => "i",
+        'う' | 'ぅ' => "u",
+        'え' | 'ぇ' => "e",
+        'お' | 'ぉ' => "o",
+        'か' | 'ゕ' => "ka",
+        'き' => "ki",
+        'く' => "ku",
+        'け' | 'ゖ' => "ke",
+        'こ' => "ko",
+        'が' => "ga",
+        'ぎ' => "gi",
+        'ぐ' => "gu",
+        'げ' => "ge",
+        'ご' => "go",
+        'さ' => "sa",
+        'し' => "shi",
+        'す' => "su",
+        'せ' => "se",
+        'そ' => "so",
+        'ざ' => "za",
+        'じ' | 'ぢ' => "ji",
+        'ず' | 'づ' => "zu",
+        'ぜ' => "ze",
+        'ぞ' => "zo",
+        'た' => "ta",
+        'ち' => "chi",
+        'つ' | 'っ' => "tsu",
+        'て' => "te",
+        'と' => "to",
+        'だ' => "da",
+        'で' => "de",
+        'ど' => "do",
+        'な' => "na",
+        'に' => "ni",
+        'ぬ' => "nu",
+        'ね' => "ne",
+        'の' => "no",
+        'は' => "ha",
+        'ひ' => "hi",
+        'ふ' => "fu",
+        'へ' => "he",
+        'ほ' => "ho",
+        'ば' => "ba",
+        'び' => "bi",
+        'ぶ' => "bu",
+        'べ' => "be",
+        'ぼ' => "bo",
+        'ぱ' => "pa",
+        'ぴ' => "pi",
+        'ぷ' => "pu",
+        'ぺ' => "pe",
+        'ぽ' => "po",
+        'ま' => "ma",
+        'み' => "mi",
+        'む' => "mu",
+        'め' => "me",
+        'も' => "mo",
+        'や' | 'ゃ' => "ya",
+        'ゆ' | 'ゅ' => "yu",
+        'よ' | 'ょ' => "yo",
+        'ら' => "ra",
+        'り' => "ri",
+        'る' => "ru",
+        'れ' => "re",
+        'ろ' => "ro",
+        'わ' | 'ゎ' => "wa",
+        'ゐ' => "i",
+        'ゑ' => "e",
+        'を' => "o",
+        'ん' => "n",
+        'ゔ' => "vu",
+        _ => "",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn transliterate_latin_letters() {
+        assert_eq!(transliterate("café_crème", false), "cafe_creme");
+        assert_eq!(transliterate("Straße", false), "Strasse");
+        assert_eq!(transliterate("STRAẞE", false), "STRASSE");
+        assert_eq!(transliterate("Æther", false), "Aether");
+        assert_eq!(transliterate("Øresund_Łódź", false), "Oresund_Lodz");
+        assert_eq!(transliterate("ﬁle²", false), "file2");
+        assert_eq!(transliterate("ユーザー漢字😀", false), "ユーザー漢字😀");
+    }
+    #[test]
+    fn romanize_kana_optionally() {
+        assert_eq!(transliterate("ユーザー", true), "yuza");
+        assert_eq!(transliterate("ユーザーID", true), "yuzaID");
+        assert_eq!(transliterate("しゃしん", true), "shashin");
+        assert_eq!(transliterate("きょう", true), "kyou");
+        assert_eq!(transliterate("マッチ", true), "matchi");
+        assert_eq!(transliterate("ロック", true), "rokku");
+        assert_eq!(transliterate("ファイル", true), "fairu");
+        assert_eq!(transliterate("パーティー", true), "pati");
+        assert_eq!(transliterate("ウィンドウ", true), "windou");
+        assert_eq!(transliterate("ｶﾞｿﾞﾘﾝ", true), "gazorin");
+        assert_eq!(transliterate("検索キー", true), "検索ki");
+    }
+}
@@ -0,0 +1,98 @@
+use std::collections::HashMap;
+
+/// Trie is a set of patterns to find the longest one at a position in a single walk.
+///
+/// The result does not depend on the order the patterns are inserted.
+#[derive(Debug, Clone)]
+pub(crate) struct Trie {
+    nodes: Vec<Node>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Node {
+    children: HashMap<char, usize>,
+    // index of the pattern which ends at this node
+    pattern: Option<usize>,
+}
+
+impl Trie {
+    pub(crate) const ROOT: usize = 0;
+
+    pub(crate) fn new() -> Self {
+        Self {
+            nodes: vec![Node::default()],
+        }
+    }
+    /// insert the pattern with its index, returns false if the same pattern is already inserted
+    pub(crate) fn insert(&mut self, pattern: &str, index: usize) -> bool {
+        let mut node = Self::ROOT;
+        for c in pattern.chars() {
+            node = match self.nodes[node].children.get(&c) {
+                Some(next) => *next,
+                None => {
+                    self.nodes.push(Node::default());
+                    let next = self.nodes.len() - 1;
+                    self.nodes[node].children.insert(c, next);
+                    next
+                }
+            };
+        }
+        if node == Self::ROOT || self.nodes[node].pattern.is_some() {
+            return false;
+        }
+        self.nodes[node].pattern = Some(index);
+        true
+    }
+    pub(crate) fn step(&self, node: usize, c: char) -> Option<usize> {
+        self.nodes[node].children.get(&c).copied()
+    }
+    /// index of the pattern which ends at the node
+    pub(crate) fn pattern(&self, node: usize) -> Option<usize> {
+        self.nodes[node].pattern
+    }
+    /// the longest pattern at the head of source accepted by is_end, with its length in bytes
+    pub(crate) fn longest_prefix(
+        &self,
+        source: &str,
+        is_end: impl Fn(usize) -> bool,
+    ) -> Option<(usize, usize)> {
+        let mut node = Self::ROOT;
+        let mut found = None;
+        for (i, c) in source.char_indices() {
+            node = match self.step(node, c) {
+                Some(next) => next,
+                None => break,
+            };
+            let end = i + c.len_utf8();
+            if let Some(pattern) = self.pattern(node) {
+                if is_end(end) {
+                    found = Some((pattern, end));
+                }
+            }
+        }
+        found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn find_longest_pattern_whatever_the_order() {
+        let mut trie = Trie::new();
+        assert!(trie.insert("javascript", 0));
+        assert!(trie.insert("java", 1));
+        assert!(!trie.insert("java", 2));
+        assert!(!trie.insert("", 3));
+        assert_eq!(
+            trie.longest_prefix("javascript_code", |_| true),
+            Some((0, 10))
+        );
+        assert_eq!(trie.longest_prefix("javas", |_| true), Some((1, 4)));
+        assert_eq!(
+            trie.longest_prefix("javascript", |end| end == 4),
+            Some((1, 4))
+        );
+        assert_eq!(trie.longest_prefix("jav", |_| true), None);
+    }
+}
@@ -0,0 +1,550 @@
+/// Word is a piece of an identifier split out by `split_words`.
+///
+/// `start` and `end` are the byte range of the word in the source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Word {
+    value: String,
+    start: usize,
+    end: usize,
+    kind: WordKind,
+}
+
+/// WordKind decides how a word is rendered by the conversion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WordKind {
+    /// rendered in the case of the principal
+    #[default]
+    Normal,
+    /// a reserved identifier like `GitHub`, rendered as it is where the principal has mixed case
+    /// and in the case of the principal otherwise, like `github` in snake_case
+    Reserved,
+    /// rendered as it is for every principal
+    Verbatim,
+}
+
+impl Word {
+    pub fn new(value: impl Into<String>, start: usize, end: usize) -> Self {
+        Self {
+            value: value.into(),
+            start,
+            end,
+            kind: WordKind::Normal,
+        }
+    }
+    pub fn with_kind(mut self, kind: WordKind) -> Self {
+        self.kind = kind;
+        self
+    }
+    pub fn kind(&self) -> WordKind {
+        self.kind
+    }
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+    pub fn start(&self) -> usize {
+        self.start
+    }
+    pub fn end(&self) -> usize {
+        self.end
+    }
+}
+
+/// Acronym decides where a run of upper case characters followed by lower case characters is split.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Acronym {
+    /// the run ends one before the last upper case character
+    ///
+    /// `HTTPServer` -> `HTTP`, `Server`
+    #[default]
+    Keep,
+    /// every upper case character of the run is a word
+    ///
+    /// `HTTPServer` -> `H`, `T`, `T`, `P`, `Server`
+    Split,
+}
+
+/// Digit decides where a run of digits is split.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Digit {
+    /// digits belong to the previous word
+    ///
+    /// `Vector3D` -> `Vector3`, `D` and `md5_hash` -> `md5`, `hash`
+    #[default]
+    Attach,
+    /// digits are a word
+    ///
+    /// `Vector3D` -> `Vector`, `3`, `D` and `md5_hash` -> `md`, `5`, `hash`
+    Separate,
+    /// digits are a word only if they are between letters
+    ///
+    /// `ipv4Address` -> `ipv`, `4`, `Address` and `md5_hash` -> `md5`, `hash`
+    BetweenLetters,
+}
+
+/// Caseless decides how letters without case, like Japanese or Chinese, are handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Caseless {
+    /// a run of caseless letters is a word
+    ///
+    /// `ユーザーID` -> `ユーザー`, `ID`
+    #[default]
+    Keep,
+    /// caseless letters are rejected by the fallible APIs like `try_convert`
+    Reject,
+}
+
+/// SplitOptions is the rule of word boundaries used by `split_words_with`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SplitOptions {
+    acronym: Acronym,
+    digit: Digit,
+    caseless: Caseless,
+}
+
+impl SplitOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn acronym(mut self, acronym: Acronym) -> Self {
+        self.acronym = acronym;
+        self
+    }
+    pub fn digit(mut self, digit: Digit) -> Self {
+        self.digit = digit;
+        self
+    }
+    pub fn caseless(mut self, caseless: Caseless) -> Self {
+        self.caseless = caseless;
+        self
+    }
+    pub(crate) fn get_caseless(&self) -> Caseless {
+        self.caseless
+    }
+}
+
+/// split_words splits any naming principal into words.
+///
+/// `_`, `-`, `.`, `/` and white spaces are separators,
+/// and an upper case character after a non upper case character starts a new word.
+/// A change between cased and caseless letters also starts a new word, so `user名前` is split into `user` and `名前`,
+/// and so does a change of the script, so `dataданные` is split into `data` and `данные`.
+/// A run of upper case characters is treated as an acronym, so `ABCData` is split into `ABC` and `Data`.
+/// ## Example
+/// ```rust
+/// use npc::word::split_words;
+///
+/// let words = split_words("helloWorld_good-bye");
+/// let words = words.iter().map(|w| w.as_str()).collect::<Vec<_>>();
+/// assert_eq!(words, vec!["hello", "World", "good", "bye"]);
+/// ```
+pub fn split_words(source: &str) -> Vec<Word> {
+    split_words_with(source, &SplitOptions::default())
+}
+
+/// split_words_with splits source into words with the given options.
+///
+/// ## Example
+/// ```rust
+/// use npc::word::{split_words_with, Acronym, SplitOptions};
+///
+/// let options = SplitOptions::new().acronym(Acronym::Split);
+/// let words = split_words_with("HTTPServer", &options);
+/// let words = words.iter().map(|w| w.as_str()).collect::<Vec<_>>();
+/// assert_eq!(words, vec!["H", "T", "T", "P", "Server"]);
+/// ```
+pub fn split_words_with(source: &str, options: &SplitOptions) -> Vec<Word> {
+    let chars = source.char_indices().collect::<Vec<_>>();
+    let upper_run_before_lower = upper_run_before_lower(&chars);
+    let digit_run_between_letters = digit_run_between_letters(&chars);
+    let mut words = Vec::new();
+    let mut start = None;
+    let mut prev: Option<char> = None;
+    let mut skip_until = 0;
+    for (k, &(i, c)) in chars.iter().enumerate() {
+        if k < skip_until {
+            continue;
+        }
+        if c == VERBATIM_START {
+            if let Some((word, next)) = verbatim_word(source, &chars, k) {
+                if let Some(s) = start.take() {
+                    words.push(Word::new(&source[s..i], s, i));
+                }
+                words.push(word);
+                prev = None;
+                skip_until = next;
+                continue;
+            }
+        }
+        if is_separator(c) {
+            if let Some(s) = start.take() {
+                words.push(Word::new(&source[s..i], s, i));
+            }
+            prev = None;
+            continue;
+        }
+        let is_boundary = match prev {
+            Some(p) if c.is_numeric() != p.is_numeric() && options.digit != Digit::Attach => {
+                // the digit run is at k when it starts, at k - 1 when it ends
+                let digit_at = if c.is_numeric() { k } else { k - 1 };
+                options.digit == Digit::Separate || digit_run_between_letters[digit_at]
+            }
+            Some(p)
+                if c.is_alphabetic() && p.is_alphabetic() && is_caseless(c) != is_caseless(p) =>
+            {
+                true
+            }
+            Some(p) if is_script_change(p, c) => true,
+            Some(p) if c.is_uppercase() && !p.is_uppercase() => true,
+            Some(p) if c.is_uppercase() && p.is_uppercase() => match options.acronym {
+                Acronym::Keep => chars.get(k + 1).is_some_and(|(_, n)| n.is_lowercase()),
+                Acronym::Split => upper_run_before_lower[k],
+            },
+            _ => false,
+        };
+        match start {
+            Some(s) if is_boundary => {
+                words.push(Word::new(&source[s..i], s, i));
+                start = Some(i);
+            }
+            None => start = Some(i),
+            _ => {}
+        }
+        prev = Some(c);
+    }
+    if let Some(s) = start {
+        words.push(Word::new(&source[s..], s, source.len()));
+    }
+    words
+}
+
+// the verbatim word masked from k, with the characters which are not separator nor alphanumeric after it,
+// and the index of the next character
+fn verbatim_word(source: &str, chars: &[(usize, char)], k: usize) -> Option<(Word, usize)> {
+    let close = k + chars[k..].iter().position(|(_, c)| *c == VERBATIM_END)?;
+    let start = chars[k].0 + VERBATIM_START.len_utf8();
+    let mut value = source[start..chars[close].0].to_string();
+    let mut next = close + 1;
+    while let Some(&(_, c)) = chars.get(next) {
+        if is_separator(c) || c.is_alphanumeric() || c == VERBATIM_START {
+            break;
+        }
+        value.push(c);
+        next += 1;
+    }
+    let end = chars.get(next).map_or(source.len(), |(i, _)| *i);
+    Some((
+        Word::new(value, start, end).with_kind(WordKind::Verbatim),
+        next,
+    ))
+}
+
+// whether each character is in a run of upper case characters followed by a lower case character
+fn upper_run_before_lower(chars: &[(usize, char)]) -> Vec<bool> {
+    let mut result = vec![false; chars.len()];
+    for k in (0..chars.len()).rev() {
+        let (_, c) = chars[k];
+        if !c.is_uppercase() {
+            continue;
+        }
+        result[k] = match chars.get(k + 1) {
+            Some((_, n)) if n.is_lowercase() => true,
+            Some((_, n)) if n.is_uppercase() => result[k + 1],
+            _ => false,
+        };
+    }
+    result
+}
+
+// whether each character is in a run of digits between letters
+fn digit_run_between_letters(chars: &[(usize, char)]) -> Vec<bool> {
+    let mut result = vec![false; chars.len()];
+    let mut k = 0;
+    while k < chars.len() {
+        if !chars[k].1.is_numeric() {
+            k += 1;
+            continue;
+        }
+        let start = k;
+        while k < chars.len() && chars[k].1.is_numeric() {
+            k += 1;
+        }
+        let after_letter = start > 0 && chars[start - 1].1.is_alphabetic();
+        let before_letter = chars.get(k).is_some_and(|(_, c)| c.is_alphabetic());
+        if after_letter && before_letter {
+            result[start..k].iter_mut().for_each(|b| *b = true);
+        }
+    }
+    result
+}
+
+/// letters without case, like Japanese kana and kanji
+pub(crate) fn is_caseless(c: char) -> bool {
+    c.is_alphabetic() && !c.is_uppercase() && !c.is_lowercase()
+}
+
+// whether the letters are written in different scripts, like Latin and Cyrillic
+fn is_script_change(prev: char, c: char) -> bool {
+    match (script(prev), script(c)) {
+        (Some(p), Some(c)) => p != c,
+        _ => false,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Script {
+    Latin,
+    Greek,
+    Cyrillic,
+    Armenian,
+    Georgian,
+    Hangul,
+    // kanji and kana are mixed in a Japanese word like `ユーザー名`
+    HanKana,
+}
+
+// the script of the letter, None for the scripts which are not distinguished
+fn script(c: char) -> Option<Script> {
+    if !c.is_alphabetic() {
+        return None;
+    }
+    let script = match c as u32 {
+        0x41..=0x5A | 0x61..=0x7A | 0xC0..=0x24F | 0x1E00..=0x1EFF => Script::Latin,
+        0x2C60..=0x2C7F | 0xA720..=0xA7FF | 0xFF21..=0xFF3A | 0xFF41..=0xFF5A => Script::Latin,
+        0x370..=0x3FF | 0x1F00..=0x1FFF => Script::Greek,
+        0x400..=0x52F | 0x1C80..=0x1C8F | 0x2DE0..=0x2DFF | 0xA640..=0xA69F => Script::Cyrillic,
+        0x530..=0x58F => Script::Armenian,
+        0x10A0..=0x10FF | 0x1C90..=0x1CBF | 0x2D00..=0x2D2F => Script::Georgian,
+        0x1100..=0x11FF | 0x3130..=0x318F | 0xA960..=0xA97F | 0xAC00..=0xD7FF => Script::Hangul,
+        0x3005..=0x3007 | 0x3040..=0x30FF | 0x31F0..=0x31FF | 0x3400..=0x4DBF => Script::HanKana,
+        0x4E00..=0x9FFF | 0xF900..=0xFAFF | 0xFF66..=0xFF9F | 0x20000..=0x3FFFF => Script::HanKana,
+        _ => return None,
+    };
+    Some(script)
+}
+
+/// VERBATIM_START and VERBATIM_END enclose a part of the source which is split out as one verbatim word.
+///
+/// They are private use characters, and `convert` removes them from the source before the pre-converters,
+/// so only the parts masked by a pre-converter like `IgnoreWordsConverter` are verbatim.
+pub(crate) const VERBATIM_START: char = '\u{E000}';
+pub(crate) const VERBATIM_END: char = '\u{E001}';
+
+/// mask encloses the source with `VERBATIM_START` and `VERBATIM_END`
+pub(crate) fn mask(source: &str) -> String {
+    format!("{}{}{}", VERBATIM_START, source, VERBATIM_END)
+}
+
+/// unmask removes `VERBATIM_START` and `VERBATIM_END` from the source
+pub(crate) fn unmask(source: &str) -> String {
+    source.replace([VERBATIM_START, VERBATIM_END], "")
+}
+
+/// map_unmasked applies f to each part out of the masked parts, and keeps the masked parts as they are.
+///
+/// A `VERBATIM_START` which is not closed is not a mask, the same as `split_words`.
+pub(crate) fn map_unmasked(source: &str, mut f: impl FnMut(&str) -> String) -> String {
+    let mut result = String::with_capacity(source.len());
+    let mut rest = source;
+    while let Some(start) = rest.find(VERBATIM_START) {
+        let Some(len) = rest[start..].find(VERBATIM_END) else {
+            break;
+        };
+        let end = start + len + VERBATIM_END.len_utf8();
+        result.push_str(&f(&rest[..start]));
+        result.push_str(&rest[start..end]);
+        rest = &rest[end..];
+    }
+    result.push_str(&f(rest));
+    result
+}
+
+pub(crate) fn is_separator(c: char) -> bool {
+    matches!(c, '_' | '-' | '.' | '/') || c.is_whitespace()
+}
+
+/// count separators at the head and the tail of source
+pub(crate) fn edge_separators(source: &str) -> (usize, usize) {
+    let leading = source.chars().take_while(|c| is_separator(*c)).count();
+    if leading == source.chars().count() {
+        return (leading, 0);
+    }
+    let trailing = source
+        .chars()
+        .rev()
+        .take_while(|c| is_separator(*c))
+        .count();
+    (leading, trailing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    fn values(source: &str) -> Vec<String> {
+        split_words(source)
+            .into_iter()
+            .map(|w| w.as_str().to_string())
+            .collect()
+    }
+    #[test]
+    fn split_words_by_separators_and_case() {
+        assert_eq!(values("snake_case"), vec!["snake", "case"]);
+        assert_eq!(values("-chain-case"), vec!["chain", "case"]);
+        assert_eq!(values("dot.case"), vec!["dot", "case"]);
+        assert_eq!(values("path/case"), vec!["path", "case"]);
+        assert_eq!(values("Title  Case"), vec!["Title", "Case"]);
+        assert_eq!(values("CONSTANT_CASE_"), vec!["CONSTANT", "CASE"]);
+        assert_eq!(values("camelCase"), vec!["camel", "Case"]);
+        assert_eq!(values("PascalCase"), vec!["Pascal", "Case"]);
+        assert_eq!(values("internetIP"), vec!["internet", "IP"]);
+        assert_eq!(values("A_data"), vec!["A", "data"]);
+        assert_eq!(values("utf8String"), vec!["utf8", "String"]);
+        assert!(values("").is_empty());
+        assert!(values("__").is_empty());
+    }
+    #[test]
+    fn split_words_treat_upper_case_run_as_acronym() {
+        assert_eq!(values("ABCData"), vec!["ABC", "Data"]);
+        assert_eq!(values("HTTPServer"), vec!["HTTP", "Server"]);
+        assert_eq!(
+            values("UKaiUseGitHub"),
+            vec!["U", "Kai", "Use", "Git", "Hub"]
+        );
+        assert_eq!(values("CONSTANT"), vec!["CONSTANT"]);
+    }
+    #[test]
+    fn split_words_with_split_acronym() {
+        let options = SplitOptions::new().acronym(Acronym::Split);
+        let values = |source: &str| {
+            split_words_with(source, &options)
+                .into_iter()
+                .map(|w| w.as_str().to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(values("ABCData"), vec!["A", "B", "C", "Data"]);
+        assert_eq!(values("HTTPServer"), vec!["H", "T", "T", "P", "Server"]);
+        // runs which are not followed by lower case are kept as it is
+        assert_eq!(values("internetIP"), vec!["internet", "IP"]);
+        assert_eq!(values("CONSTANT_CASE"), vec!["CONSTANT", "CASE"]);
+    }
+    #[test]
+    fn split_words_with_digit_policy() {
+        let values = |source: &str, digit: Digit| {
+            split_words_with(source, &SplitOptions::new().digit(digit))
+                .into_iter()
+                .map(|w| w.as_str().to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(values("utf8String", Digit::Attach), vec!["utf8", "String"]);
+        assert_eq!(values("md5_hash", Digit::Attach), vec!["md5", "hash"]);
+        assert_eq!(values("Vector3D", Digit::Attach), vec!["Vector3", "D"]);
+        assert_eq!(
+            values("ipv4Address", Digit::Attach),
+            vec!["ipv4", "Address"]
+        );
+        assert_eq!(values("version2", Digit::Attach), vec!["version2"]);
+
+        assert_eq!(
+            values("utf8String", Digit::Separate),
+            vec!["utf", "8", "String"]
+        );
+        assert_eq!(values("md5_hash", Digit::Separate), vec!["md", "5", "hash"]);
+        assert_eq!(
+            values("Vector3D", Digit::Separate),
+            vec!["Vector", "3", "D"]
+        );
+        assert_eq!(
+            values("ipv4Address", Digit::Separate),
+            vec!["ipv", "4", "Address"]
+        );
+        assert_eq!(values("version2", Digit::Separate), vec!["version", "2"]);
+        assert_eq!(values("3dModel", Digit::Separate), vec!["3", "d", "Model"]);
+
+        assert_eq!(
+            values("utf8String", Digit::BetweenLetters),
+            vec!["utf", "8", "String"]
+        );
+        assert_eq!(
+            values("md5_hash", Digit::BetweenLetters),
+            vec!["md5", "hash"]
+        );
+        assert_eq!(
+            values("Vector3D", Digit::BetweenLetters),
+            vec!["Vector", "3", "D"]
+        );
+        assert_eq!(
+            values("ipv4Address", Digit::BetweenLetters),
+            vec!["ipv", "4", "Address"]
+        );
+        assert_eq!(values("version2", Digit::BetweenLetters), vec!["version2"]);
+        assert_eq!(
+            values("3dModel", Digit::BetweenLetters),
+            vec!["3d", "Model"]
+        );
+    }
+    #[test]
+    fn split_words_by_caseless_letters() {
+        assert_eq!(values("ユーザーID"), vec!["ユーザー", "ID"]);
+        assert_eq!(values("user名前"), vec!["user", "名前"]);
+        assert_eq!(values("ユーザー名Data"), vec!["ユーザー名", "Data"]);
+        assert_eq!(
+            values("getユーザー_by_id"),
+            vec!["get", "ユーザー", "by", "id"]
+        );
+        assert_eq!(values("日本語2List"), vec!["日本語2", "List"]);
+        assert_eq!(values("プログラム言語"), vec!["プログラム言語"]);
+    }
+    #[test]
+    fn split_words_by_script_change() {
+        assert_eq!(
+            values("dataданные userκόσμε"),
+            vec!["data", "данные", "user", "κόσμε"]
+        );
+        assert_eq!(values("ДанныеData"), vec!["Данные", "Data"]);
+        assert_eq!(values("userΑΒΓ"), vec!["user", "ΑΒΓ"]);
+        assert_eq!(values("사용자id"), vec!["사용자", "id"]);
+        assert_eq!(values("ユーザー名"), vec!["ユーザー名"]);
+        assert_eq!(values("straße_café"), vec!["straße", "café"]);
+        assert_eq!(values("данные2user"), vec!["данные2user"]);
+    }
+    #[test]
+    fn split_words_keep_masked_part_as_verbatim_word() {
+        let source = format!("{}! GoodBye", mask("Hello-World"));
+        let words = split_words(&source);
+        assert_eq!(words[0].as_str(), "Hello-World!");
+        assert_eq!(words[0].kind(), WordKind::Verbatim);
+        assert_eq!(values(&source), vec!["Hello-World!", "Good", "Bye"]);
+        let source = format!("my{}Name", mask("URL"));
+        assert_eq!(values(&source), vec!["my", "URL", "Name"]);
+        // not closed
+        assert_eq!(
+            values("\u{E000}hello_world"),
+            vec!["\u{E000}hello", "world"]
+        );
+    }
+    #[test]
+    fn map_only_unmasked_parts() {
+        let source = format!("a-{}-b{}", mask("c-d"), mask("e"));
+        assert_eq!(
+            map_unmasked(&source, |part| part.replace('-', "_")),
+            format!("a_{}_b{}", mask("c-d"), mask("e"))
+        );
+        assert_eq!(
+            map_unmasked("a-\u{E000}b-c", |part| part.replace('-', "_")),
+            "a_\u{E000}b_c"
+        );
+        assert_eq!(unmask(&source), "a-c-d-be");
+    }
+    #[test]
+    fn split_words_keep_byte_range_of_source() {
+        let words = split_words("_helloWorld");
+        assert_eq!(words[0], Word::new("hello", 1, 6));
+        assert_eq!(words[1], Word::new("World", 6, 11));
+    }
+    #[test]
+    fn edge_separators_count_head_and_tail() {
+        assert_eq!(edge_separators("_snake_case"), (1, 0));
+        assert_eq!(edge_separators("ABC-Data_"), (0, 1));
+        assert_eq!(edge_separators("__"), (2, 0));
+        assert_eq!(edge_separators(""), (0, 0));
+    }
+}